@@ -0,0 +1,31 @@
+drop table event_teams;
+
+create table data_old (
+    team integer not null,
+    match_number integer not null,
+    alliance text check(alliance in ('red', 'blue')) not null,
+    left_tarmac text check (left_tarmac in ('yes', 'no')) not null,
+    auto_high_made integer not null,
+    auto_high_missed integer not null,
+    auto_low_made integer not null,
+    auto_low_missed integer not null,
+    teleop_high_made integer not null,
+    teleop_high_missed integer not null,
+    teleop_low_made integer not null,
+    teleop_low_missed integer not null,
+    climb text check (climb in ('no', 'failed', 'low', 'mid', 'high', 'traversal')) not null,
+    notes text not null,
+    primary key (team, match_number)
+);
+
+-- only one scout's entry per robot and match can be kept
+insert or ignore into data_old
+select
+    team, match_number, alliance, left_tarmac,
+    auto_high_made, auto_high_missed, auto_low_made, auto_low_missed,
+    teleop_high_made, teleop_high_missed, teleop_low_made, teleop_low_missed,
+    climb, notes
+from data;
+
+drop table data;
+alter table data_old rename to data;
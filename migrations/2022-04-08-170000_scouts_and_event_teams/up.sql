@@ -0,0 +1,41 @@
+-- sqlite can't change a primary key in place, so rebuild the table with the scout as part of the key
+-- so more than one scout can cover the same robot in the same match
+create table data_new (
+    team integer not null,
+    match_number integer not null,
+    alliance text check(alliance in ('red', 'blue')) not null,
+    left_tarmac text check (left_tarmac in ('yes', 'no')) not null,
+    auto_high_made integer not null,
+    auto_high_missed integer not null,
+    auto_low_made integer not null,
+    auto_low_missed integer not null,
+    teleop_high_made integer not null,
+    teleop_high_missed integer not null,
+    teleop_low_made integer not null,
+    teleop_low_missed integer not null,
+    climb text check (climb in ('no', 'failed', 'low', 'mid', 'high', 'traversal')) not null,
+    notes text not null,
+    scout text not null default '',
+    primary key (team, match_number, scout)
+);
+
+insert into data_new (
+    team, match_number, alliance, left_tarmac,
+    auto_high_made, auto_high_missed, auto_low_made, auto_low_missed,
+    teleop_high_made, teleop_high_missed, teleop_low_made, teleop_low_missed,
+    climb, notes
+)
+select
+    team, match_number, alliance, left_tarmac,
+    auto_high_made, auto_high_missed, auto_low_made, auto_low_missed,
+    teleop_high_made, teleop_high_missed, teleop_low_made, teleop_low_missed,
+    climb, notes
+from data;
+
+drop table data;
+alter table data_new rename to data;
+
+-- teams attending the event, used to catch typos in submitted team numbers
+create table event_teams (
+    team integer primary key not null
+);
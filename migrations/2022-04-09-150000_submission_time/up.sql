@@ -0,0 +1,2 @@
+-- unix time in seconds, older rows get 0 since there's no way to know when they were sent
+alter table data add column submitted_at integer not null default 0
@@ -0,0 +1,4 @@
+drop trigger data_notes_update;
+drop trigger data_notes_delete;
+drop trigger data_notes_insert;
+drop table notes_fts;
@@ -0,0 +1,27 @@
+-- full text index over the notes, with the key columns stored alongside so results can be linked back
+-- (data doesn't have an integer primary key, so its rowids aren't stable enough for an external content table)
+create virtual table notes_fts using fts5(
+    notes,
+    team unindexed,
+    match_number unindexed,
+    scout unindexed
+);
+
+insert into notes_fts (notes, team, match_number, scout)
+select notes, team, match_number, scout from data;
+
+create trigger data_notes_insert after insert on data begin
+    insert into notes_fts (notes, team, match_number, scout)
+    values (new.notes, new.team, new.match_number, new.scout);
+end;
+
+create trigger data_notes_delete after delete on data begin
+    delete from notes_fts
+    where team = old.team and match_number = old.match_number and scout = old.scout;
+end;
+
+create trigger data_notes_update after update on data begin
+    update notes_fts
+    set notes = new.notes, team = new.team, match_number = new.match_number, scout = new.scout
+    where team = old.team and match_number = old.match_number and scout = old.scout;
+end;
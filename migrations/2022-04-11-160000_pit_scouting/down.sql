@@ -0,0 +1,3 @@
+drop index pit_photos_team;
+drop table pit_photos;
+drop table pit_data;
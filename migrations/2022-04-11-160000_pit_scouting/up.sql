@@ -0,0 +1,23 @@
+-- what a team says about their robot when asked in the pits, one report per team
+-- (a newer report replaces the old one, since teams change their robots during an event)
+create table pit_data (
+    team integer primary key not null,
+    drivetrain text not null,
+    -- pounds, left empty if the team doesn't know
+    weight integer,
+    shooter text not null,
+    climb text check (climb in ('no', 'failed', 'low', 'mid', 'high', 'traversal')) not null,
+    programming_language text not null,
+    notes text not null,
+    scout text not null,
+    submitted_at integer not null
+);
+
+-- robot photos, the files themselves are kept on disk in the photos directory
+create table pit_photos (
+    filename text primary key not null,
+    team integer not null,
+    uploaded_at integer not null
+);
+
+create index pit_photos_team on pit_photos (team);
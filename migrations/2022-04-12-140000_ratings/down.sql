@@ -0,0 +1,5 @@
+alter table data drop column penalties_drawn;
+alter table data drop column speed;
+alter table data drop column defense_resilience;
+alter table data drop column defense_played;
+alter table data drop column driver_skill;
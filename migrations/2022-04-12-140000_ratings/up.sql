@@ -0,0 +1,7 @@
+-- 1 to 5 ratings of the things counters can't capture, empty when the scout didn't rate it
+-- (and for every entry from before these were added)
+alter table data add column driver_skill integer check (driver_skill between 1 and 5);
+alter table data add column defense_played integer check (defense_played between 1 and 5);
+alter table data add column defense_resilience integer check (defense_resilience between 1 and 5);
+alter table data add column speed integer check (speed between 1 and 5);
+alter table data add column penalties_drawn integer check (penalties_drawn between 1 and 5);
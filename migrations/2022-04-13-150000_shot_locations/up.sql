@@ -0,0 +1,15 @@
+-- where on the field each shot was taken from, tapped on the field diagram on the form.
+-- x runs along the length of the field from the red alliance wall and y across it, both from 0 to 1,
+-- and sequence is the order the taps came in so an auto routine can be followed shot by shot
+create table shots (
+    team integer not null,
+    match_number integer not null,
+    scout text not null,
+    sequence integer not null,
+    phase text check (phase in ('auto', 'teleop')) not null,
+    x real not null,
+    y real not null,
+    made boolean not null,
+    primary key (team, match_number, scout, sequence),
+    foreign key (team, match_number, scout) references data (team, match_number, scout) on delete cascade
+);
@@ -0,0 +1 @@
+drop table match_events;
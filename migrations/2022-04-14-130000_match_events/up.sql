@@ -0,0 +1,15 @@
+-- every button pressed in live scouting mode, with the time in seconds since the start of the match.
+-- the counters in data are worked out from these when a submission has them
+create table match_events (
+    team integer not null,
+    match_number integer not null,
+    scout text not null,
+    sequence integer not null,
+    time real not null,
+    kind text check (kind in (
+        'high_made', 'high_missed', 'low_made', 'low_missed',
+        'climb_start', 'climb_end', 'defense_start', 'defense_end'
+    )) not null,
+    primary key (team, match_number, scout, sequence),
+    foreign key (team, match_number, scout) references data (team, match_number, scout) on delete cascade
+);
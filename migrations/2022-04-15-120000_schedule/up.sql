@@ -0,0 +1,10 @@
+-- the qualification schedule, pasted in from the event so strategy sheets know who is in each match
+create table schedule (
+    match_number integer primary key not null,
+    red1 integer not null,
+    red2 integer not null,
+    red3 integer not null,
+    blue1 integer not null,
+    blue2 integer not null,
+    blue3 integer not null
+);
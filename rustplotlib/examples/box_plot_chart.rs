@@ -0,0 +1,50 @@
+use charts::{Chart, BoxPlotView, ScaleBand, ScaleLinear};
+
+fn main() {
+    // Define chart related sizes.
+    let width = 800;
+    let height = 600;
+    let (top, right, bottom, left) = (90, 40, 50, 60);
+
+    // Create a band scale that maps ["A", "B", "C"] categories to values in [0, availableWidth]
+    // range (the width of the chart without the margins).
+    let x = ScaleBand::new()
+        .set_domain(vec![String::from("A"), String::from("B"), String::from("C")])
+        .set_range(vec![0, width - left - right]);
+
+    // Create a linear scale that will interpolate values in [0, 100] range to corresponding
+    // values in [availableHeight, 0] range (the height of the chart without the margins).
+    let y = ScaleLinear::new()
+        .set_domain(vec![0_f32, 100_f32])
+        .set_range(vec![height - top - bottom, 0]);
+
+    // You can use your own iterable as data as long as its items implement the `SampleDatum` trait.
+    // Every item is a single sample, and the view works out the distribution of each category.
+    let data = vec![
+        ("A", 42, "foo"), ("A", 55, "foo"), ("A", 48, "foo"), ("A", 61, "foo"), ("A", 50, "foo"), ("A", 95, "foo"),
+        ("B", 20, "foo"), ("B", 35, "foo"), ("B", 28, "foo"), ("B", 31, "foo"), ("B", 25, "foo"), ("B", 40, "foo"),
+        ("C", 70, "foo"), ("C", 65, "foo"), ("C", 80, "foo"), ("C", 72, "foo"), ("C", 5, "foo"), ("C", 68, "foo"),
+        ("A", 30, "bar"), ("A", 38, "bar"), ("A", 33, "bar"), ("B", 60, "bar"), ("B", 52, "bar"), ("B", 58, "bar"),
+        ("C", 45, "bar"), ("C", 50, "bar"), ("C", 39, "bar"), ("C", 47, "bar"),
+    ];
+
+    // Create a BoxPlotView that is going to show the distribution of every category and key.
+    let view = BoxPlotView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .load_data(&data).unwrap();
+
+    // Generate and save the chart.
+    Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(String::from("Box Plot"))
+        .add_legend_at(charts::AxisPosition::Top)
+        .add_view(view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label("Units of Measurement")
+        .add_bottom_axis_label("Categories")
+        .save("box-plot-chart.svg").unwrap();
+}
@@ -48,8 +48,8 @@ fn main() {
         .set_height(height)
         .set_margins(top, right, bottom, left)
         .add_title(String::from("Composite Bar + Scatter Chart"))
-        .add_view(&bar_view)                            // <-- add bar view
-        .add_view(&scatter_view)                        // <-- add scatter view
+        .add_view(bar_view)                            // <-- add bar view
+        .add_view(scatter_view)                        // <-- add scatter view
         .add_axis_bottom(&x)
         .add_axis_left(&y)
         .add_left_axis_label("Units of Measurement")
@@ -0,0 +1,43 @@
+use charts::{Chart, VerticalBarView, ScaleBand, ScaleLinear, BarLabelPosition};
+
+fn main() {
+    // Define chart related sizes.
+    let width = 800;
+    let height = 600;
+    let (top, right, bottom, left) = (90, 40, 50, 60);
+
+    // Create a band scale that maps ["A", "B", "C"] categories to values in [0, availableWidth]
+    // range (the width of the chart without the margins).
+    let x = ScaleBand::new()
+        .set_domain(vec![String::from("A"), String::from("B"), String::from("C")])
+        .set_range(vec![0, width - left - right]);
+
+    // Create a linear scale that spans both negative and positive values, since negative
+    // values are stacked down from zero while positive ones are stacked up from it.
+    let y = ScaleLinear::new()
+        .set_domain(vec![-50_f32, 100_f32])
+        .set_range(vec![height - top - bottom, 0]);
+
+    // You can use your own iterable as data as long as its items implement the `BarDatum` trait.
+    let data = vec![("A", 70, "foo"), ("B", -10, "foo"), ("C", 30, "foo"), ("A", -20, "bar"), ("B", 25, "bar"), ("A", 5, "baz"), ("C", -15, "baz")];
+
+    // Create VerticalBar view that is going to represent the data as vertical bars.
+    let view = VerticalBarView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_label_position(BarLabelPosition::Center)
+        .load_data(&data).unwrap();
+
+    // Generate and save the chart.
+    Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(String::from("Diverging Stacked Bar Chart"))
+        .add_view(view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label("Units of Measurement")
+        .add_bottom_axis_label("Categories")
+        .save("diverging-vertical-bar-chart.svg").unwrap();
+}
@@ -0,0 +1,52 @@
+use charts::{Chart, HeatmapView, ColorScale, ScaleBand};
+
+fn main() {
+    // Define chart related sizes.
+    let width = 800;
+    let height = 600;
+    let (top, right, bottom, left) = (90, 40, 50, 60);
+
+    // Create band scales for both dimensions, so that every pair of categories gets a cell.
+    let x = ScaleBand::new()
+        .set_domain(vec![String::from("A"), String::from("B"), String::from("C"), String::from("D")])
+        .set_range(vec![0, width - left - right])
+        .set_inner_padding(0_f32)
+        .set_outer_padding(0_f32);
+    let y = ScaleBand::new()
+        .set_domain(vec![String::from("W"), String::from("X"), String::from("Y"), String::from("Z")])
+        .set_range(vec![0, height - top - bottom])
+        .set_inner_padding(0_f32)
+        .set_outer_padding(0_f32);
+
+    // You can use your own iterable as data as long as its items implement the `HeatmapDatum` trait.
+    let data = vec![
+        ("A", "W", -8), ("B", "W", -3), ("C", "W", 2), ("D", "W", 6),
+        ("A", "X", -5), ("B", "X", 0), ("C", "X", 4), ("D", "X", 9),
+        ("A", "Y", -2), ("B", "Y", 3), ("C", "Y", 7), ("D", "Y", 10),
+        ("A", "Z", 1), ("B", "Z", 5), ("C", "Z", -6), ("D", "Z", -10),
+    ];
+
+    // Create a HeatmapView with a diverging color scale centered on zero.
+    let view = HeatmapView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_color_scale(ColorScale::red_blue())
+        .set_color_domain(vec![-10_f32, 0_f32, 10_f32])
+        .set_label_visibility(true)
+        .set_custom_data_label(String::from("Difference"))
+        .load_data(&data).unwrap();
+
+    // Generate and save the chart.
+    Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(String::from("Heatmap"))
+        .add_legend_at(charts::AxisPosition::Top)
+        .add_view(view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label("Rows")
+        .add_bottom_axis_label("Columns")
+        .save("heatmap-chart.svg").unwrap();
+}
@@ -0,0 +1,55 @@
+use charts::{Chart, HistogramView, HistogramMode, Binning, ScaleLinear};
+
+fn main() {
+    // Define chart related sizes.
+    let width = 800;
+    let height = 600;
+    let (top, right, bottom, left) = (90, 40, 50, 60);
+
+    // You can use your own iterable as data as long as its items implement the `HistogramDatum` trait.
+    // Every item is a single value, and the view counts how many of them fall into each bin.
+    let data = vec![
+        (12, "foo"), (15, "foo"), (18, "foo"), (21, "foo"), (22, "foo"), (24, "foo"), (25, "foo"), (27, "foo"),
+        (28, "foo"), (30, "foo"), (31, "foo"), (33, "foo"), (36, "foo"), (41, "foo"), (48, "foo"),
+        (25, "bar"), (29, "bar"), (32, "bar"), (34, "bar"), (35, "bar"), (37, "bar"), (38, "bar"), (40, "bar"),
+        (42, "bar"), (45, "bar"), (51, "bar"),
+    ];
+
+    // Create a HistogramView that splits the values into bins 5 units wide and stacks the keys.
+    let view = HistogramView::new()
+        .set_binning(Binning::Width(5_f32))
+        .set_mode(HistogramMode::Stacked);
+
+    // The view works out which domains fit the bins, so the scales can be created from them.
+    let (x_domain, y_domain) = view.get_domains(&data);
+
+    // Create a linear scale that will interpolate the binned values to corresponding
+    // values in [0, availableWidth] range (the width of the chart without the margins).
+    let x = ScaleLinear::new()
+        .set_domain(x_domain)
+        .set_range(vec![0, width - left - right]);
+
+    // Create a linear scale for the counts, inverted so that the bins grow upwards.
+    let y = ScaleLinear::new()
+        .set_domain(y_domain)
+        .set_range(vec![height - top - bottom, 0]);
+
+    let view = view
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .load_data(&data).unwrap();
+
+    // Generate and save the chart.
+    Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(String::from("Histogram"))
+        .add_legend_at(charts::AxisPosition::Top)
+        .add_view(view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label("Count")
+        .add_bottom_axis_label("Units of Measurement")
+        .save("histogram-chart.svg").unwrap();
+}
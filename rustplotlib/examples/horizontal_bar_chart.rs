@@ -33,7 +33,7 @@ fn main() {
         .set_height(height)
         .set_margins(top, right, bottom, left)
         .add_title(String::from("Horizontal Bar Chart"))
-        .add_view(&view)
+        .add_view(view)
         .add_axis_bottom(&x)
         .add_axis_top(&x)
         .add_axis_left(&y)
@@ -0,0 +1,26 @@
+use charts::{Chart, PieView};
+
+fn main() {
+    // Define chart related sizes.
+    let width = 800;
+    let height = 600;
+    let (top, right, bottom, left) = (90, 40, 50, 60);
+
+    // You can use your own iterable as data as long as its items implement the `BarDatum` trait.
+    // Every category is a slice, sized by its share of the total.
+    let data = vec![("A", 45), ("B", 30), ("C", 25), ("D", 15), ("E", 8)];
+
+    // Create a PieView, which doesn't need scales since it fits itself into the chart.
+    let view = PieView::new()
+        .load_data(&data).unwrap();
+
+    // Generate and save the chart, without any axes.
+    Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(String::from("Pie Chart"))
+        .add_legend_at(charts::AxisPosition::Top)
+        .add_view(view)
+        .save("pie-chart.svg").unwrap();
+}
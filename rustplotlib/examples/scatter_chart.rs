@@ -38,7 +38,7 @@ fn main() {
         .set_height(height)
         .set_margins(top, right, bottom, left)
         .add_title(String::from("Scatter Chart"))
-        .add_view(&scatter_view)
+        .add_view(scatter_view)
         .add_axis_bottom(&x)
         .add_axis_left(&y)
         .add_left_axis_label("Custom X Axis Label")
@@ -38,7 +38,7 @@ fn main() {
         .set_height(height)
         .set_margins(top, right, bottom, left)
         .add_title(String::from("Stacked Bar Chart"))
-        .add_view(&view)
+        .add_view(view)
         .add_axis_bottom(&x)
         .add_axis_left(&y)
         .add_left_axis_label("Units of Measurement")
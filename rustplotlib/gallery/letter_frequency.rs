@@ -43,7 +43,7 @@ fn main() -> std::io::Result<()> {
         .set_height(height)
         .set_margins(top, right, bottom, left)
         .add_title(String::from("Frequency of English Letters"))
-        .add_view(&view)
+        .add_view(view)
         .add_axis_bottom(&x)
         .add_axis_left(&y)
         .add_left_axis_label("Frequency (%)")
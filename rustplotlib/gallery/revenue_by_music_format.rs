@@ -48,7 +48,7 @@ fn main() -> std::io::Result<()> {
         .set_width(width)
         .set_height(height)
         .set_margins(top, right, bottom, left)
-        .add_view(&view)
+        .add_view(view)
         .add_axis_bottom(&x)
         .add_axis_left(&y)
         .add_legend_at(AxisPosition::Top)
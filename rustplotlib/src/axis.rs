@@ -1,15 +1,15 @@
 use std::string::ToString;
 use svg::node::element::Group;
-use svg::parser::Error;
 use svg::Node;
 use svg::node::Text as TextNode;
 use svg::node::element::Text;
 use crate::{Scale, Chart};
 use crate::components::axis::{AxisLine, AxisTick};
 use crate::scales::ScaleType;
+use crate::ChartError;
 
 /// Enum of possible axis positions on the chart.
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum AxisPosition {
     Top,
     Right,
@@ -30,7 +30,7 @@ pub struct Axis {
 
 impl Axis {
     /// Create a new instance of an axis for a chart based on the provided scale and position.
-    fn new<'a, T: ToString>(scale: &'a dyn Scale<T>, position: AxisPosition, chart: &Chart<'a>) -> Self {
+    fn new<T: ToString>(scale: &dyn Scale<T>, position: AxisPosition, chart: &Chart) -> Self {
         Self {
             ticks: Self::generate_ticks(scale, position),
             position,
@@ -43,22 +43,22 @@ impl Axis {
     }
 
     /// Create a new axis at the top of the chart.
-    pub fn new_top_axis<'a, T: ToString>(scale: &'a dyn Scale<T>, chart: &Chart<'a>) -> Self {
+    pub fn new_top_axis<T: ToString>(scale: &dyn Scale<T>, chart: &Chart) -> Self {
         Self::new(scale, AxisPosition::Top, chart)
     }
 
     /// Create a new axis to the right of the chart.
-    pub fn new_right_axis<'a, T: ToString>(scale: &'a dyn Scale<T>, chart: &Chart<'a>) -> Self {
+    pub fn new_right_axis<T: ToString>(scale: &dyn Scale<T>, chart: &Chart) -> Self {
         Self::new(scale, AxisPosition::Right, chart)
     }
 
     /// Create a new axis at the bottom of the chart.
-    pub fn new_bottom_axis<'a, T: ToString>(scale: &'a dyn Scale<T>, chart: &Chart<'a>) -> Self {
+    pub fn new_bottom_axis<T: ToString>(scale: &dyn Scale<T>, chart: &Chart) -> Self {
         Self::new(scale, AxisPosition::Bottom, chart)
     }
 
     /// Create a new axis to the left of the chart.
-    pub fn new_left_axis<'a, T: ToString>(scale: &'a dyn Scale<T>, chart: &Chart<'a>) -> Self {
+    pub fn new_left_axis<T: ToString>(scale: &dyn Scale<T>, chart: &Chart) -> Self {
         Self::new(scale, AxisPosition::Left, chart)
     }
 
@@ -85,8 +85,18 @@ impl Axis {
         self.label.len() > 0
     }
 
+    /// Compute how far rotated tick labels reach away from the axis, so the axis label can clear them.
+    fn tick_label_overhang(&self) -> i32 {
+        if self.label_rotation == 0 {
+            return 0;
+        }
+        let longest = self.ticks.iter().map(|tick| tick.label_len()).max().unwrap_or(0);
+        // Tick labels are 12px, so a character is about 7px wide.
+        ((longest * 7) as f32 * (self.label_rotation as f32).to_radians().sin().abs()) as i32
+    }
+
     /// Compute the length of the axis.
-    fn get_axis_length<'a>(position: AxisPosition, chart: &Chart<'a>) -> isize {
+    fn get_axis_length(position: AxisPosition, chart: &Chart) -> isize {
         if position == AxisPosition::Top || position == AxisPosition::Bottom {
             chart.get_view_width()
         } else {
@@ -95,7 +105,7 @@ impl Axis {
     }
 
     /// Generate svg for the axis.
-    pub fn to_svg(&self) -> Result<Group, Error> {
+    pub fn to_svg(&self) -> Result<Group, ChartError> {
         let axis_class = match self.position {
             AxisPosition::Top => "x-axis",
             AxisPosition::Bottom => "x-axis",
@@ -105,16 +115,16 @@ impl Axis {
 
         let mut group = Group::new()
             .set("class", axis_class)
-            .add(self.axis_line.to_svg().unwrap());
+            .add(self.axis_line.to_svg()?);
 
         for tick in self.ticks.iter() {
-            group.append(tick.to_svg().unwrap());
+            group.append(tick.to_svg()?);
         }
 
         if self.label.len() > 0 {
             let (x, y, rotate) = match self.position {
-                AxisPosition::Top => ((self.length / 2) as i32, -32, 0),
-                AxisPosition::Bottom => ((self.length / 2) as i32, 42, 0),
+                AxisPosition::Top => ((self.length / 2) as i32, -32 - self.tick_label_overhang(), 0),
+                AxisPosition::Bottom => ((self.length / 2) as i32, 42 + self.tick_label_overhang(), 0),
                 AxisPosition::Left => (-(self.length as i32 / 2), -42, -90),
                 AxisPosition::Right => ((self.length as i32 / 2), -42, 90),
             };
@@ -134,7 +144,7 @@ impl Axis {
     }
 
     /// Generate ticks for the axis based on the scale and position.
-    fn generate_ticks<'a, T: ToString>(scale: &'a dyn Scale<T>, position: AxisPosition) -> Vec<AxisTick> {
+    fn generate_ticks<T: ToString>(scale: &dyn Scale<T>, position: AxisPosition) -> Vec<AxisTick> {
         let mut ticks = Vec::new();
         let label_offset = {
             if position == AxisPosition::Top || position == AxisPosition::Bottom {
@@ -163,7 +173,7 @@ impl Axis {
     }
 
     /// Generate the line that represents the axis.
-    fn get_axis_line<'a>(position: AxisPosition, chart: &Chart<'a>) -> AxisLine {
+    fn get_axis_line(position: AxisPosition, chart: &Chart) -> AxisLine {
         match position {
             AxisPosition::Top => AxisLine::new(0_f32, 0_f32, chart.get_view_width() as f32, 0_f32),
             AxisPosition::Right => AxisLine::new(0_f32, 0_f32, 0_f32, chart.get_view_height() as f32),
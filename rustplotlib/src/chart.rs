@@ -1,5 +1,6 @@
 use std::string::ToString;
 use std::ffi::OsStr;
+use std::fs;
 use std::path::Path;
 use svg;
 use svg::node::element::Group;
@@ -11,6 +12,8 @@ use crate::views::View;
 use crate::axis::AxisPosition;
 use crate::legend::Legend;
 use crate::components::legend::LegendEntry;
+use crate::raster::svg_to_png;
+use crate::ChartError;
 
 /// Define the orientation enum to aid in rendering and business logic.
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -20,8 +23,9 @@ pub enum Orientation {
 }
 
 /// The Chart struct definition.
-/// A Chart is the smallest entity that can be saved (the bigger one is a Page (TBD)).
-pub struct Chart<'a> {
+/// A Chart is the smallest entity that can be saved (the bigger ones are a Grid and a Page).
+/// It owns its views, so it can be returned from a function or rendered on another thread.
+pub struct Chart {
     margin_top: isize,
     margin_bottom: isize,
     margin_right: isize,
@@ -33,11 +37,13 @@ pub struct Chart<'a> {
     y_axis_left: Option<Axis>,
     y_axis_right: Option<Axis>,
     legend_position: Option<AxisPosition>,
-    views: Vec<&'a dyn View<'a>>,
+    views: Vec<Box<dyn View>>,
     title: String,
+    // An axis that was given a label before it was added, reported when the chart gets rendered.
+    missing_axis: Option<AxisPosition>,
 }
 
-impl<'a> Chart<'a> {
+impl Chart {
     /// Create a new instance of a chart with default sizes.
     pub fn new() -> Self {
         Self {
@@ -54,6 +60,7 @@ impl<'a> Chart<'a> {
             legend_position: None,
             views: Vec::new(),
             title: String::new(),
+            missing_axis: None,
         }
     }
 
@@ -84,32 +91,32 @@ impl<'a> Chart<'a> {
         self
     }
 
-    /// Add the dataset to the chart's view.
-    pub fn add_view(mut self, view: &'a dyn View<'a>) -> Self {
-        self.views.push(view);
+    /// Add the dataset to the chart's view, taking ownership of it.
+    pub fn add_view(mut self, view: impl View + 'static) -> Self {
+        self.views.push(Box::new(view));
         self
     }
 
     /// Add an axis at the bottom of the chart.
-    pub fn add_axis_bottom<T: ToString>(mut self, scale: &'a dyn Scale<T>) -> Self {
+    pub fn add_axis_bottom<T: ToString>(mut self, scale: &dyn Scale<T>) -> Self {
         self.x_axis_bottom = Some(Axis::new_bottom_axis(scale, &self));
         self
     }
 
     /// Add an axis at the left of the chart.
-    pub fn add_axis_left<T: ToString>(mut self, scale: &'a dyn Scale<T>) -> Self {
+    pub fn add_axis_left<T: ToString>(mut self, scale: &dyn Scale<T>) -> Self {
         self.y_axis_left = Some(Axis::new_left_axis(scale, &self));
         self
     }
 
     /// Add an axis at the top of the chart.
-    pub fn add_axis_top<T: ToString>(mut self, scale: &'a dyn Scale<T>) -> Self {
+    pub fn add_axis_top<T: ToString>(mut self, scale: &dyn Scale<T>) -> Self {
         self.x_axis_top = Some(Axis::new_top_axis(scale, &self));
         self
     }
 
     /// Add an axis at the right of the chart.
-    pub fn add_axis_right<T: ToString>(mut self, scale: &'a dyn Scale<T>) -> Self {
+    pub fn add_axis_right<T: ToString>(mut self, scale: &dyn Scale<T>) -> Self {
         self.y_axis_right = Some(Axis::new_right_axis(scale, &self));
         self
     }
@@ -119,7 +126,7 @@ impl<'a> Chart<'a> {
         if let Some(ref mut axis) = self.y_axis_right {
             axis.set_axis_label(label.to_string())
         } else {
-            panic!("You cannot add a label to right axis without adding an axis first.")
+            self.missing_axis.get_or_insert(AxisPosition::Right);
         }
         self
     }
@@ -129,7 +136,7 @@ impl<'a> Chart<'a> {
         if let Some(ref mut axis) = self.y_axis_left {
             axis.set_axis_label(label.to_string())
         } else {
-            panic!("You cannot add a label to left axis without adding an axis first.")
+            self.missing_axis.get_or_insert(AxisPosition::Left);
         }
         self
     }
@@ -139,7 +146,7 @@ impl<'a> Chart<'a> {
         if let Some(ref mut axis) = self.x_axis_top {
             axis.set_axis_label(label.to_string())
         } else {
-            panic!("You cannot add a label to top axis without adding an axis first.")
+            self.missing_axis.get_or_insert(AxisPosition::Top);
         }
         self
     }
@@ -149,7 +156,7 @@ impl<'a> Chart<'a> {
         if let Some(ref mut axis) = self.x_axis_bottom {
             axis.set_axis_label(label.to_string())
         } else {
-            panic!("You cannot add a label to bottom axis without adding an axis first.")
+            self.missing_axis.get_or_insert(AxisPosition::Bottom);
         }
         self
     }
@@ -194,6 +201,11 @@ impl<'a> Chart<'a> {
         self.height - self.margin_top - self.margin_bottom
     }
 
+    /// Return the legend entries of every view in the chart.
+    pub(crate) fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        self.views.iter().map(|view| view.get_legend_entries()).flatten().collect()
+    }
+
     /// Set legend position at the specified side of the chart.
     pub fn add_legend_at(mut self, position: AxisPosition) -> Self {
         self.legend_position = Some(position);
@@ -273,7 +285,11 @@ impl<'a> Chart<'a> {
     }
 
     /// Generate the SVG for the chart and its components.
-    pub fn to_svg(&self) -> Result<Group, String> {
+    pub fn to_svg(&self) -> Result<Group, ChartError> {
+        if let Some(position) = self.missing_axis {
+            return Err(ChartError::MissingAxis(position));
+        }
+
         let mut group = Group::new()
             .set("class", "g-chart");
 
@@ -296,25 +312,25 @@ impl<'a> Chart<'a> {
         }
 
         if let Some(ref axis) = self.x_axis_top {
-            let mut axis_group = axis.to_svg().unwrap();
+            let mut axis_group = axis.to_svg()?;
             axis_group.assign("transform", format!("translate({},{})", self.margin_left, self.margin_top));
             group.append(axis_group);
         };
 
         if let Some(ref axis) = self.x_axis_bottom {
-            let mut axis_group = axis.to_svg().unwrap();
+            let mut axis_group = axis.to_svg()?;
             axis_group.assign("transform", format!("translate({},{})", self.margin_left, self.height - self.margin_bottom));
             group.append(axis_group);
         };
 
         if let Some(ref axis) = self.y_axis_left {
-            let mut axis_group = axis.to_svg().unwrap();
+            let mut axis_group = axis.to_svg()?;
             axis_group.assign("transform", format!("translate({},{})", self.margin_left, self.margin_top));
             group.append(axis_group);
         };
 
         if let Some(ref axis) = self.y_axis_right {
-            let mut axis_group = axis.to_svg().unwrap();
+            let mut axis_group = axis.to_svg()?;
             axis_group.assign("transform", format!("translate({},{})", self.width - self.margin_right, self.margin_top));
             group.append(axis_group);
         };
@@ -323,8 +339,10 @@ impl<'a> Chart<'a> {
             .set("class", "g-view")
             .set("transform", format!("translate({},{})", self.margin_left, self.margin_top));
 
+        let inner_width = (self.width - self.margin_left - self.margin_right) as f32;
+        let inner_height = (self.height - self.margin_top - self.margin_bottom) as f32;
         for view in self.views.iter() {
-            view_group.append(view.to_svg()?);
+            view_group.append(view.to_svg_in_area(inner_width, inner_height)?);
         }
         group.append(view_group);
 
@@ -401,8 +419,7 @@ impl<'a> Chart<'a> {
                 }
             };
 
-            let legend_entries = self.views.iter().map(|view| view.get_legend_entries()).flatten().collect::<Vec<LegendEntry>>();
-            let legend = Legend::new(legend_entries, width as usize);
+            let legend = Legend::new(self.get_legend_entries(), width as usize);
             let mut legend_group = legend.to_svg()?;
             legend_group.assign("transform", format!("translate({},{})", x_offset, y_offset));
 
@@ -412,27 +429,33 @@ impl<'a> Chart<'a> {
         Ok(group)
     }
 
-    /// Save the chart to a file
-    pub fn save<P>(self, path: P) -> Result<(), String> where
+    /// Generate a standalone SVG document for the chart, sized to the chart.
+    pub fn to_document(&self) -> Result<svg::Document, ChartError> {
+        Ok(svg::Document::new()
+            .set("width", self.width)
+            .set("height", self.height)
+            .set("viewBox", (0, 0, self.width, self.height))
+            .add(self.to_svg()?))
+    }
+
+    /// Rasterize the chart to PNG bytes.
+    pub fn to_png(&self) -> Result<Vec<u8>, ChartError> {
+        svg_to_png(&self.to_document()?.to_string())
+    }
+
+    /// Save the chart to a file, as SVG or PNG depending on the extension.
+    pub fn save<P>(self, path: P) -> Result<(), ChartError> where
         P: AsRef<Path>
     {
-        match path.as_ref().extension().and_then(OsStr::to_str) {
-            Some("svg") => {
-                match self.to_svg() {
-                    Ok(svg_content) => {
-                        let document = svg::Document::new()
-                            .set("width", self.width)
-                            .set("height", self.height)
-                            .set("viewBox", (0, 0, self.width, self.height))
-                            .add(svg_content);
-
-                        svg::save(path, &document).unwrap()
-                    },
-                    Err(e) => return Err(format!("Encountered an error while saving the chart: {:?}", e)),
-                }
-            },
-            _ => {},
-        };
-        Ok(())
+        save_document(&self.to_document()?, path)
+    }
+}
+
+/// Save an SVG document to a file, rasterizing it first if the extension is png.
+pub(crate) fn save_document<P: AsRef<Path>>(document: &svg::Document, path: P) -> Result<(), ChartError> {
+    match path.as_ref().extension().and_then(OsStr::to_str) {
+        Some("svg") => Ok(svg::save(path, document)?),
+        Some("png") => Ok(fs::write(path, svg_to_png(&document.to_string())?)?),
+        other => Err(ChartError::UnsupportedFormat(other.unwrap_or("").to_string())),
     }
 }
@@ -1,3 +1,5 @@
+pub(crate) mod scale;
+
 /// A struct that represents a color.
 #[derive(Debug)]
 pub struct Color {
@@ -68,4 +70,13 @@ impl Color {
     pub fn as_hex(&self) -> String {
         String::from(&self.hex)
     }
+
+    /// Check whether dark text would be hard to read on top of the color.
+    pub fn is_dark(&self) -> bool {
+        match scale::parse_hex(&self.hex) {
+            // Perceived brightness, weighting the parts the way eyes see them.
+            Some((red, green, blue)) => 0.299 * red + 0.587 * green + 0.114 * blue < 128_f32,
+            None => false,
+        }
+    }
 }
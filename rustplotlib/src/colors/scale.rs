@@ -0,0 +1,194 @@
+use crate::colors::Color;
+
+/// A continuous color scale that maps numbers to colors by interpolating
+/// between evenly spaced color stops.
+#[derive(Clone, Debug)]
+pub struct ColorScale {
+    stops: Vec<(f32, f32, f32)>,
+    domain: Vec<f32>,
+}
+
+impl ColorScale {
+    /// Create a color scale that goes through the given hex colors in order.
+    /// Colors that aren't valid hex strings are left out.
+    pub fn from_hex_stops(stops: Vec<&str>) -> Self {
+        Self {
+            stops: stops.iter().filter_map(|hex| parse_hex(hex)).collect(),
+            domain: vec![0_f32, 1_f32],
+        }
+    }
+
+    /// A sequential scale from dark purple through blue and green to yellow,
+    /// which reads well in grayscale and for color blind viewers.
+    pub fn viridis() -> Self {
+        Self::from_hex_stops(vec!["#440154", "#482878", "#3e4989", "#31688e", "#26828e", "#1f9e89", "#35b779", "#6ece58", "#b5de2b", "#fde725"])
+    }
+
+    /// A sequential scale from white to dark blue.
+    pub fn blues() -> Self {
+        Self::from_hex_stops(vec!["#f7fbff", "#deebf7", "#c6dbef", "#9ecae1", "#6baed6", "#4292c6", "#2171b5", "#08519c", "#08306b"])
+    }
+
+    /// A sequential scale from light yellow through orange to dark red.
+    pub fn yellow_orange_red() -> Self {
+        Self::from_hex_stops(vec!["#ffffcc", "#ffeda0", "#fed976", "#feb24c", "#fd8d3c", "#fc4e2a", "#e31a1c", "#bd0026", "#800026"])
+    }
+
+    /// A diverging scale from dark red through white to dark blue. Works best
+    /// with a three value domain, so that the middle value is white.
+    pub fn red_blue() -> Self {
+        Self::from_hex_stops(vec!["#67001f", "#b2182b", "#d6604d", "#f4a582", "#fddbc7", "#f7f7f7", "#d1e5f0", "#92c5de", "#4393c3", "#2166ac", "#053061"])
+    }
+
+    /// Set the domain of the scale. Two values map to the two ends of the scale, and
+    /// a third one in between maps to its middle, as used by diverging scales.
+    pub fn set_domain(mut self, domain: Vec<f32>) -> Self {
+        self.domain = domain;
+        self
+    }
+
+    /// Get the domain of the scale.
+    pub fn domain(&self) -> &Vec<f32> {
+        &self.domain
+    }
+
+    /// Get the color for the given value. Values outside the domain get the color of the closest end.
+    pub fn scale(&self, value: &f32) -> Color {
+        let position = match self.domain.as_slice() {
+            [start, middle, _] if value < middle => 0.5 * normalize(*start, *middle, *value),
+            [_, middle, end] => 0.5 + 0.5 * normalize(*middle, *end, *value),
+            [start, end, ..] => normalize(*start, *end, *value),
+            _ => 0_f32,
+        };
+
+        let (red, green, blue) = match self.stops.len() {
+            0 => (0_f32, 0_f32, 0_f32),
+            1 => self.stops[0],
+            count => {
+                let scaled = position.clamp(0_f32, 1_f32) * (count - 1) as f32;
+                let index = (scaled.floor() as usize).min(count - 2);
+                let t = scaled - index as f32;
+                let (from, to) = (self.stops[index], self.stops[index + 1]);
+                (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t, from.2 + (to.2 - from.2) * t)
+            },
+        };
+
+        Color { hex: format!("#{:02x}{:02x}{:02x}", red.round() as u8, green.round() as u8, blue.round() as u8) }
+    }
+
+    /// Get colors evenly spread over the whole scale, from its start to its end.
+    pub fn sample(&self, count: usize) -> Vec<Color> {
+        let domain = self.domain.as_slice();
+        let (start, end) = match domain {
+            [start, .., end] => (*start, *end),
+            _ => (0_f32, 1_f32),
+        };
+        (0..count)
+            .map(|i| self.scale(&(start + (end - start) * i as f32 / (count.max(2) - 1) as f32)))
+            .collect()
+    }
+}
+
+/// Map a value in [start, end] to [0, 1].
+fn normalize(start: f32, end: f32, value: f32) -> f32 {
+    if end == start {
+        0.5
+    } else {
+        (value - start) / (end - start)
+    }
+}
+
+/// Read the red, green and blue parts of a "#rrggbb" string.
+pub(crate) fn parse_hex(hex: &str) -> Option<(f32, f32, f32)> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let part = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok().map(|part| part as f32);
+    Some((part(0)?, part(2)?, part(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn black_to_white() -> ColorScale {
+        ColorScale::from_hex_stops(vec!["#000000", "#ffffff"])
+    }
+
+    #[test]
+    fn parse_hex_reads_six_digit_colors() {
+        assert_eq!(parse_hex("#ff8000"), Some((255_f32, 128_f32, 0_f32)));
+        assert_eq!(parse_hex("#FF8000"), Some((255_f32, 128_f32, 0_f32)));
+    }
+
+    #[test]
+    fn parse_hex_rejects_anything_else() {
+        assert_eq!(parse_hex(""), None);
+        assert_eq!(parse_hex("ff8000"), None);
+        assert_eq!(parse_hex("#f80"), None);
+        assert_eq!(parse_hex("#ff80001"), None);
+        assert_eq!(parse_hex("#gg8000"), None);
+        // six bytes, but not six hex digits
+        assert_eq!(parse_hex("#ééé"), None);
+    }
+
+    #[test]
+    fn invalid_stops_are_left_out() {
+        let scale = ColorScale::from_hex_stops(vec!["#000000", "nope", "#ffffff"]);
+        assert_eq!(scale.scale(&0.5).as_hex(), "#808080");
+    }
+
+    #[test]
+    fn scale_interpolates_between_stops() {
+        let scale = black_to_white();
+        assert_eq!(scale.scale(&0_f32).as_hex(), "#000000");
+        assert_eq!(scale.scale(&0.5).as_hex(), "#808080");
+        assert_eq!(scale.scale(&1_f32).as_hex(), "#ffffff");
+    }
+
+    #[test]
+    fn values_outside_the_domain_get_the_closest_end() {
+        let scale = black_to_white().set_domain(vec![10_f32, 20_f32]);
+        assert_eq!(scale.scale(&-5_f32).as_hex(), "#000000");
+        assert_eq!(scale.scale(&100_f32).as_hex(), "#ffffff");
+        assert_eq!(scale.scale(&f32::NAN).as_hex(), "#000000");
+    }
+
+    #[test]
+    fn a_single_value_domain_maps_to_the_middle() {
+        let scale = black_to_white().set_domain(vec![5_f32, 5_f32]);
+        assert_eq!(scale.scale(&5_f32).as_hex(), "#808080");
+    }
+
+    #[test]
+    fn unusable_domains_and_stops_still_give_a_color() {
+        assert_eq!(black_to_white().set_domain(Vec::new()).scale(&3_f32).as_hex(), "#000000");
+        assert_eq!(black_to_white().set_domain(vec![1_f32]).scale(&3_f32).as_hex(), "#000000");
+        assert_eq!(ColorScale::from_hex_stops(Vec::new()).scale(&0.5).as_hex(), "#000000");
+        assert_eq!(ColorScale::from_hex_stops(vec!["#123456"]).scale(&0.9).as_hex(), "#123456");
+    }
+
+    #[test]
+    fn three_value_domains_put_the_middle_value_in_the_middle() {
+        let scale = ColorScale::from_hex_stops(vec!["#ff0000", "#ffffff", "#0000ff"]).set_domain(vec![-1_f32, 0_f32, 4_f32]);
+        assert_eq!(scale.scale(&-1_f32).as_hex(), "#ff0000");
+        assert_eq!(scale.scale(&0_f32).as_hex(), "#ffffff");
+        assert_eq!(scale.scale(&2_f32).as_hex(), "#8080ff");
+        assert_eq!(scale.scale(&4_f32).as_hex(), "#0000ff");
+    }
+
+    #[test]
+    fn sample_spans_the_whole_scale() {
+        let colors: Vec<String> = black_to_white().set_domain(vec![0_f32, 10_f32]).sample(3).iter().map(|c| c.as_hex()).collect();
+        assert_eq!(colors, vec!["#000000", "#808080", "#ffffff"]);
+        assert!(black_to_white().sample(0).is_empty());
+        assert_eq!(black_to_white().sample(1)[0].as_hex(), "#000000");
+    }
+
+    #[test]
+    fn dark_colors_are_told_apart() {
+        assert!(black_to_white().scale(&0_f32).is_dark());
+        assert!(!black_to_white().scale(&1_f32).is_dark());
+    }
+}
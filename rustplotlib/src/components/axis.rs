@@ -4,6 +4,7 @@ use svg::node::element::Text;
 use svg::Node;
 use format_num::NumberFormat;
 use crate::axis::AxisPosition;
+use crate::ChartError;
 
 /// A simple struct that represents an axis line.
 pub(crate) struct AxisLine {
@@ -20,7 +21,7 @@ impl AxisLine {
     }
 
     /// Render the axis line to svg.
-    pub fn to_svg(&self) -> Result<Line, String> {
+    pub fn to_svg(&self) -> Result<Line, ChartError> {
         let line = Line::new()
             .set("x1", self.x1)
             .set("y1", self.y1)
@@ -62,18 +63,24 @@ impl AxisTick {
         self.label_rotation = rotation;
     }
 
+    /// Get the number of characters in the unformatted label.
+    pub fn label_len(&self) -> usize {
+        self.label.chars().count()
+    }
+
     /// Set label rotation.
     pub fn set_label_format(&mut self, format: &str) {
         self.label_format = Some(format.to_owned());
     }
 
     /// Render the axis tick to svg.
-    pub fn to_svg(&self) -> Result<Group, String> {
-        let formatted_label = if self.label_format.is_some() {
-            let formatter = NumberFormat::new();
-            formatter.format(self.label_format.as_ref().unwrap(), self.label.parse::<f64>().unwrap()).replace('G', "B")
-        } else {
-            self.label.to_owned()
+    pub fn to_svg(&self) -> Result<Group, ChartError> {
+        let formatted_label = match self.label_format {
+            Some(ref format) => {
+                let value = self.label.parse::<f64>().map_err(|_| ChartError::InvalidTickLabel(self.label.clone()))?;
+                NumberFormat::new().format(format, value).replace('G', "B")
+            },
+            None => self.label.to_owned(),
         };
         let offsets: (f32, f32);
         let tick_line_p2: (isize, isize);
@@ -91,7 +98,12 @@ impl AxisTick {
                 offsets = (self.tick_offset, 0_f32);
                 tick_line_p2 = (0, 6);
                 tick_label_offset = (0, self.label_offset as isize);
-                tick_label_text_anchor = "middle";
+                // rotated labels hang off the tick instead of being centered on it
+                tick_label_text_anchor = match self.label_rotation {
+                    r if r < 0 => "end",
+                    r if r > 0 => "start",
+                    _ => "middle",
+                };
             },
             AxisPosition::Right => {
                 offsets = (0_f32, self.tick_offset);
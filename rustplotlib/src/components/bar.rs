@@ -5,6 +5,7 @@ use svg::node::Text as TextNode;
 use svg::node::element::Text;
 use crate::components::DatumRepresentation;
 use crate::chart::Orientation;
+use crate::ChartError;
 
 /// Set the position of a bar's label.
 #[derive(Copy, Clone, Debug)]
@@ -16,6 +17,17 @@ pub enum BarLabelPosition {
     EndOutside,
 }
 
+/// Set how the bars of different keys within a category are arranged.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BarMode {
+    /// Bars are stacked on top of each other in a single bar, with negative values below zero.
+    Stacked,
+    /// Bars are stacked like in [BarMode::Stacked], but scaled so every category adds up to 1.
+    Normalized,
+    /// Bars are placed side by side, splitting the category's band between the keys.
+    Grouped,
+}
+
 /// Represents a block within a bar.
 /// The first tuple element represents the starting position, the second
 /// one is the size of that block and the third one is the color.
@@ -66,7 +78,7 @@ impl Bar {
 
 impl DatumRepresentation for Bar {
 
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
         let (bar_group_offset_x, bar_group_offset_y) = {
             match self.orientation {
                 Orientation::Vertical => (self.offset, 0_f32),
@@ -112,7 +124,7 @@ impl DatumRepresentation for Bar {
 
                 let label_text = match &self.rounding_precision {
                     None => block.2.to_string(),
-                    Some(nr_of_digits) => format!("{:.1$}", block.2.to_string().parse::<f32>().unwrap(), nr_of_digits)
+                    Some(nr_of_digits) => format!("{:.1$}", block.2, nr_of_digits)
                 };
 
                 let label = Text::new()
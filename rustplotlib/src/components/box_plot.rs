@@ -0,0 +1,102 @@
+use svg::node::Node;
+use svg::node::element::{Circle, Group, Line, Rectangle};
+use crate::components::DatumRepresentation;
+use crate::ChartError;
+
+/// Represents a single box in a box plot. All the values are already scaled
+/// to positions along the Y axis.
+#[derive(Debug)]
+pub struct BoxPlot {
+    q1: f32,
+    median: f32,
+    q3: f32,
+    low_whisker: f32,
+    high_whisker: f32,
+    outliers: Vec<f32>,
+    color: String,
+    box_width: f32,
+    offset: f32,
+}
+
+impl BoxPlot {
+    pub fn new(
+        (q1, median, q3): (f32, f32, f32),
+        (low_whisker, high_whisker): (f32, f32),
+        outliers: Vec<f32>,
+        color: String,
+        box_width: f32,
+        offset: f32,
+    ) -> Self {
+        Self {
+            q1,
+            median,
+            q3,
+            low_whisker,
+            high_whisker,
+            outliers,
+            color,
+            box_width,
+            offset,
+        }
+    }
+
+    /// Create a horizontal line across the box at the given position, spanning `fraction` of its width.
+    fn horizontal_line(&self, y: f32, fraction: f32) -> Line {
+        let inset = self.box_width * (1_f32 - fraction) / 2_f32;
+        Line::new()
+            .set("x1", inset)
+            .set("x2", self.box_width - inset)
+            .set("y1", y)
+            .set("y2", y)
+            .set("stroke", "#333")
+            .set("shape-rendering", "crispEdges")
+    }
+
+    /// Create a vertical line through the middle of the box.
+    fn vertical_line(&self, y1: f32, y2: f32) -> Line {
+        Line::new()
+            .set("x1", self.box_width / 2_f32)
+            .set("x2", self.box_width / 2_f32)
+            .set("y1", y1)
+            .set("y2", y2)
+            .set("stroke", "#333")
+            .set("shape-rendering", "crispEdges")
+    }
+}
+
+impl DatumRepresentation for BoxPlot {
+
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new()
+            .set("transform", format!("translate({},{})", self.offset, 0))
+            .set("class", "box-plot");
+
+        // Whiskers go from the edges of the box to the furthest samples that aren't outliers.
+        group.append(self.vertical_line(self.low_whisker, self.q1));
+        group.append(self.vertical_line(self.q3, self.high_whisker));
+        group.append(self.horizontal_line(self.low_whisker, 0.5));
+        group.append(self.horizontal_line(self.high_whisker, 0.5));
+
+        group.append(Rectangle::new()
+            .set("x", 0)
+            .set("y", self.q1.min(self.q3))
+            .set("width", self.box_width)
+            .set("height", (self.q3 - self.q1).abs())
+            .set("fill", self.color.as_ref())
+            .set("stroke", "#333")
+            .set("shape-rendering", "crispEdges"));
+
+        group.append(self.horizontal_line(self.median, 1_f32).set("stroke-width", 2));
+
+        for outlier in self.outliers.iter() {
+            group.append(Circle::new()
+                .set("cx", self.box_width / 2_f32)
+                .set("cy", *outlier)
+                .set("r", 3)
+                .set("fill", "none")
+                .set("stroke", self.color.as_ref()));
+        }
+
+        Ok(group)
+    }
+}
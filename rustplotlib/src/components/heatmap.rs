@@ -0,0 +1,60 @@
+use svg::node::Node;
+use svg::node::Text as TextNode;
+use svg::node::element::{Group, Rectangle, Text};
+use crate::components::DatumRepresentation;
+use crate::ChartError;
+
+/// Represents a single cell of a heatmap. The positions are already
+/// scaled to the chart's coordinates.
+#[derive(Debug)]
+pub struct HeatmapCell {
+    x_range: (f32, f32),
+    y_range: (f32, f32),
+    color: String,
+    label: Option<(String, String)>,
+}
+
+impl HeatmapCell {
+    /// Create a new cell, optionally with a label and the color of its text.
+    pub fn new(x_range: (f32, f32), y_range: (f32, f32), color: String, label: Option<(String, String)>) -> Self {
+        Self {
+            x_range,
+            y_range,
+            color,
+            label,
+        }
+    }
+}
+
+impl DatumRepresentation for HeatmapCell {
+
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new()
+            .set("class", "heatmap-cell");
+
+        let (x, width) = (self.x_range.0.min(self.x_range.1), (self.x_range.1 - self.x_range.0).abs());
+        let (y, height) = (self.y_range.0.min(self.y_range.1), (self.y_range.1 - self.y_range.0).abs());
+
+        group.append(Rectangle::new()
+            .set("x", x)
+            .set("y", y)
+            .set("width", width)
+            .set("height", height)
+            .set("fill", self.color.as_ref())
+            .set("shape-rendering", "crispEdges"));
+
+        if let Some((ref label, ref label_color)) = self.label {
+            group.append(Text::new()
+                .set("x", x + width / 2_f32)
+                .set("y", y + height / 2_f32)
+                .set("dy", ".35em")
+                .set("text-anchor", "middle")
+                .set("font-family", "sans-serif")
+                .set("fill", label_color.as_ref())
+                .set("font-size", "12px")
+                .add(TextNode::new(label)));
+        }
+
+        Ok(group)
+    }
+}
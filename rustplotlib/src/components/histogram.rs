@@ -0,0 +1,49 @@
+use svg::node::Node;
+use svg::node::element::{Group, Rectangle};
+use crate::components::DatumRepresentation;
+use crate::ChartError;
+
+/// Represents the part of a histogram bin that belongs to a single key.
+/// The positions are already scaled to the chart's coordinates.
+#[derive(Debug)]
+pub struct HistogramBin {
+    x_start: f32,
+    x_end: f32,
+    y_start: f32,
+    y_end: f32,
+    color: String,
+    opacity: f32,
+}
+
+impl HistogramBin {
+    pub fn new((x_start, x_end): (f32, f32), (y_start, y_end): (f32, f32), color: String, opacity: f32) -> Self {
+        Self {
+            x_start,
+            x_end,
+            y_start,
+            y_end,
+            color,
+            opacity,
+        }
+    }
+}
+
+impl DatumRepresentation for HistogramBin {
+
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new()
+            .set("class", "histogram-bin");
+
+        group.append(Rectangle::new()
+            .set("x", self.x_start.min(self.x_end))
+            .set("y", self.y_start.min(self.y_end))
+            .set("width", (self.x_end - self.x_start).abs())
+            .set("height", (self.y_end - self.y_start).abs())
+            .set("fill", self.color.as_ref())
+            .set("fill-opacity", self.opacity)
+            .set("stroke", "#fff")
+            .set("shape-rendering", "crispEdges"));
+
+        Ok(group)
+    }
+}
@@ -3,6 +3,7 @@ use svg::Node;
 use svg::node::Text as TextNode;
 use svg::node::element::Text;
 use crate::MarkerType;
+use crate::ChartError;
 
 /// Represents the possible marker types that a legend entry can have.
 pub enum LegendMarkerType {
@@ -10,6 +11,9 @@ pub enum LegendMarkerType {
     Square,
     X,
     Line,
+    /// A bar of colors running from a low value to a high value, for continuous color scales.
+    /// Holds the colors in order, and the labels of the low and the high end.
+    ColorBar(Vec<String>, String, String),
 }
 
 impl From<MarkerType> for LegendMarkerType {
@@ -22,6 +26,9 @@ impl From<MarkerType> for LegendMarkerType {
     }
 }
 
+const COLOR_BAR_WIDTH: usize = 100;
+const AVG_LETTER_WIDTH: usize = 7; // this is for the default sans-serif 12px font + some buffer
+
 /// Represents an entry in the chart's legend.
 pub struct LegendEntry {
     marker_type: LegendMarkerType,
@@ -45,14 +52,40 @@ impl LegendEntry {
         }
     }
 
+    /// Return the label of the legend entry.
+    pub fn get_label(&self) -> &str {
+        &self.label
+    }
+
     /// Return legend entry width to compute the placement of legend entries on the chart.
     pub fn get_width(&self) -> usize {
         // TODO ideally, compute the length of the given `label` in the given font and size
-        let avg_letter_width = 7; // this is for the default sans-serif 12px font + some buffer
-        avg_letter_width * self.label.len() + self.marker_size * 2 + self.marker_to_label_gap
+        AVG_LETTER_WIDTH * self.label.len() + self.get_marker_width() + self.marker_to_label_gap
+    }
+
+    /// Return the width of the marker, which for a color bar includes its end labels.
+    fn get_marker_width(&self) -> usize {
+        match self.marker_type {
+            LegendMarkerType::ColorBar(_, ref low, ref high) => {
+                AVG_LETTER_WIDTH * (low.len() + high.len()) + COLOR_BAR_WIDTH + 2 * self.marker_to_label_gap
+            },
+            _ => self.marker_size * 2,
+        }
+    }
+
+    /// Create a text element next to the marker.
+    fn text(&self, x: usize, content: &str) -> Text {
+        Text::new()
+            .set("x", x)
+            .set("y", self.marker_size)
+            .set("dy", ".35em")
+            .set("font-family", "sans-serif")
+            .set("fill", "#777")
+            .set("font-size", "12px")
+            .add(TextNode::new(content))
     }
 
-    pub fn to_svg(&self) -> Result<Group, String> {
+    pub fn to_svg(&self) -> Result<Group, ChartError> {
         let mut group = Group::new()
             .set("class", "legend-entry");
 
@@ -104,18 +137,28 @@ impl LegendEntry {
                     .set("stroke-width", "2px")
                     .set("stroke-dasharray", self.stroke_type.as_ref())
             ),
+            LegendMarkerType::ColorBar(ref colors, ref low, ref high) => {
+                let bar_start = AVG_LETTER_WIDTH * low.len() + self.marker_to_label_gap;
+                let segment_width = COLOR_BAR_WIDTH as f32 / colors.len().max(1) as f32;
+
+                group.append(self.text(0, low));
+                for (i, color) in colors.iter().enumerate() {
+                    group.append(
+                        Rectangle::new()
+                            .set("x", bar_start as f32 + i as f32 * segment_width)
+                            .set("y", 0)
+                            .set("width", segment_width)
+                            .set("height", 2 * self.marker_size)
+                            .set("fill", color.as_ref())
+                            .set("stroke", "none")
+                            .set("shape-rendering", "crispEdges")
+                    );
+                }
+                group.append(self.text(bar_start + COLOR_BAR_WIDTH + self.marker_to_label_gap, high));
+            },
         }
 
-        group.append(
-            Text::new()
-                .set("x", 2 * self.marker_size + self.marker_to_label_gap)
-                .set("y", self.marker_size)
-                .set("dy", ".35em")
-                .set("font-family", "sans-serif")
-                .set("fill", "#777")
-                .set("font-size", "12px")
-                .add(TextNode::new(self.label.clone()))
-        );
+        group.append(self.text(self.get_marker_width() + self.marker_to_label_gap, &self.label));
 
         Ok(group)
     }
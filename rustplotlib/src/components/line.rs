@@ -4,6 +4,7 @@ use svg::node::element::path::Data;
 use svg::node::Node;
 use crate::components::DatumRepresentation;
 use crate::components::scatter::ScatterPoint;
+use crate::ChartError;
 
 /// Represents a point in a scatter plot.
 #[derive(Debug)]
@@ -26,7 +27,7 @@ impl<T: Display, U: Display> LineSeries<T, U> {
 
 impl<T: Display, U: Display> DatumRepresentation for LineSeries<T, U> {
 
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
         let mut group = Group::new()
             .set("class", "line");
 
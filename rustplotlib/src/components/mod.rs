@@ -1,4 +1,5 @@
 use svg::node::element::Group;
+use crate::ChartError;
 
 pub(crate) mod bar;
 pub(crate) mod axis;
@@ -6,8 +7,13 @@ pub(crate) mod scatter;
 pub(crate) mod line;
 pub(crate) mod legend;
 pub(crate) mod area;
+pub(crate) mod box_plot;
+pub(crate) mod violin;
+pub(crate) mod histogram;
+pub(crate) mod heatmap;
+pub(crate) mod pie;
 
 /// A trait that defines behavior of chart components.
 pub trait DatumRepresentation {
-    fn to_svg(&self) -> Result<Group, String>;
+    fn to_svg(&self) -> Result<Group, ChartError>;
 }
\ No newline at end of file
@@ -0,0 +1,157 @@
+use std::f32::consts::PI;
+use svg::node::Node;
+use svg::node::Text as TextNode;
+use svg::node::element::{Group, Path, Polyline, Text};
+use svg::node::element::path::Data;
+use crate::components::DatumRepresentation;
+use crate::ChartError;
+
+/// How far beyond the slice a leader line goes before turning towards its label.
+pub(crate) const LEADER_LINE_LENGTH: f32 = 14_f32;
+/// How long the horizontal part of a leader line is.
+pub(crate) const LEADER_LINE_TAIL: f32 = 12_f32;
+
+/// Set the position of a slice's label.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SliceLabelPosition {
+    /// Labels are written on the slices themselves.
+    Inside,
+    /// Labels are written around the pie, connected to their slices by leader lines.
+    Outside,
+}
+
+/// Represents a single slice of a pie or donut, centered on the origin.
+/// Angles are in radians, going clockwise from the top.
+#[derive(Debug)]
+pub struct Slice {
+    start_angle: f32,
+    end_angle: f32,
+    outer_radius: f32,
+    inner_radius: f32,
+    color: String,
+    label: Option<String>,
+    label_position: SliceLabelPosition,
+}
+
+impl Slice {
+    pub fn new(
+        (start_angle, end_angle): (f32, f32),
+        (inner_radius, outer_radius): (f32, f32),
+        color: String,
+        label: Option<String>,
+        label_position: SliceLabelPosition,
+    ) -> Self {
+        Self {
+            start_angle,
+            end_angle,
+            outer_radius,
+            inner_radius,
+            color,
+            label,
+            label_position,
+        }
+    }
+
+    /// Return the point at the given angle and distance from the center.
+    fn point(angle: f32, radius: f32) -> (f32, f32) {
+        (radius * angle.sin(), -radius * angle.cos())
+    }
+
+    /// Build the outline of the slice.
+    fn outline(&self) -> Data {
+        let (outer, inner) = (self.outer_radius, self.inner_radius);
+
+        // A slice that goes all the way around can't be drawn as a single arc, since its
+        // ends meet, so it is drawn as two halves instead.
+        if self.end_angle - self.start_angle >= 2_f32 * PI - 1e-4 {
+            let mut data = Data::new()
+                .move_to(Self::point(0_f32, outer))
+                .elliptical_arc_to((outer, outer, 0_f32, 1_f32, 1_f32, Self::point(PI, outer).0, Self::point(PI, outer).1))
+                .elliptical_arc_to((outer, outer, 0_f32, 1_f32, 1_f32, Self::point(0_f32, outer).0, Self::point(0_f32, outer).1))
+                .close();
+            if inner > 0_f32 {
+                data = data
+                    .move_to(Self::point(0_f32, inner))
+                    .elliptical_arc_to((inner, inner, 0_f32, 1_f32, 0_f32, Self::point(PI, inner).0, Self::point(PI, inner).1))
+                    .elliptical_arc_to((inner, inner, 0_f32, 1_f32, 0_f32, Self::point(0_f32, inner).0, Self::point(0_f32, inner).1))
+                    .close();
+            }
+            return data;
+        }
+
+        let large_arc = if self.end_angle - self.start_angle > PI { 1_f32 } else { 0_f32 };
+        let outer_end = Self::point(self.end_angle, outer);
+        let inner_start = Self::point(self.start_angle, inner);
+
+        let data = Data::new()
+            .move_to(Self::point(self.start_angle, outer))
+            .elliptical_arc_to((outer, outer, 0_f32, large_arc, 1_f32, outer_end.0, outer_end.1));
+
+        if inner > 0_f32 {
+            let inner_end = Self::point(self.end_angle, inner);
+            data.line_to(inner_end)
+                .elliptical_arc_to((inner, inner, 0_f32, large_arc, 0_f32, inner_start.0, inner_start.1))
+                .close()
+        } else {
+            data.line_to((0_f32, 0_f32)).close()
+        }
+    }
+}
+
+impl DatumRepresentation for Slice {
+
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new()
+            .set("class", "slice");
+
+        group.append(Path::new()
+            .set("d", self.outline())
+            .set("fill", self.color.as_ref())
+            .set("fill-rule", "evenodd")
+            .set("stroke", "#fff")
+            .set("stroke-width", 1));
+
+        let label = match self.label {
+            Some(ref label) => label,
+            None => return Ok(group),
+        };
+        let middle_angle = (self.start_angle + self.end_angle) / 2_f32;
+
+        let text = match self.label_position {
+            SliceLabelPosition::Inside => {
+                let (x, y) = Self::point(middle_angle, (self.inner_radius + self.outer_radius) / 2_f32);
+                Text::new()
+                    .set("x", x)
+                    .set("y", y)
+                    .set("text-anchor", "middle")
+            },
+            SliceLabelPosition::Outside => {
+                // The leader line leaves the slice from the middle of its arc and turns
+                // sideways, towards the side of the pie the slice is on.
+                let start = Self::point(middle_angle, self.outer_radius);
+                let elbow = Self::point(middle_angle, self.outer_radius + LEADER_LINE_LENGTH);
+                let (direction, anchor) = if elbow.0 >= 0_f32 { (1_f32, "start") } else { (-1_f32, "end") };
+                let end = (elbow.0 + direction * LEADER_LINE_TAIL, elbow.1);
+
+                group.append(Polyline::new()
+                    .set("points", format!("{},{} {},{} {},{}", start.0, start.1, elbow.0, elbow.1, end.0, end.1))
+                    .set("fill", "none")
+                    .set("stroke", "#999"));
+
+                Text::new()
+                    .set("x", end.0 + direction * 4_f32)
+                    .set("y", end.1)
+                    .set("text-anchor", anchor)
+            },
+        };
+
+        group.append(text
+            .set("dy", ".35em")
+            .set("font-family", "sans-serif")
+            .set("fill", if self.label_position == SliceLabelPosition::Inside { "#fff" } else { "#333" })
+            .set("font-size", "12px")
+            .add(TextNode::new(label)));
+
+        Ok(group)
+    }
+}
@@ -4,6 +4,7 @@ use svg::node::Node;
 use svg::node::Text as TextNode;
 use svg::node::element::Text;
 use crate::components::DatumRepresentation;
+use crate::ChartError;
 
 /// Define the possible types of points in a scatter plot.
 #[derive(Debug, Copy, Clone)]
@@ -81,7 +82,7 @@ impl<T: Display, U: Display> ScatterPoint<T, U> {
 
 impl<T: Display, U: Display> DatumRepresentation for ScatterPoint<T, U> {
 
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
         let mut group = Group::new()
             .set("transform", format!("translate({},{})", self.x, self.y))
             .set("class", "scatter-point");
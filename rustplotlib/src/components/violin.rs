@@ -0,0 +1,84 @@
+use svg::node::Node;
+use svg::node::element::{Circle, Group, Line, Path};
+use svg::node::element::path::Data;
+use crate::components::DatumRepresentation;
+use crate::ChartError;
+
+/// Represents a single violin in a violin plot. The outline is a list of positions along
+/// the Y axis together with how far the violin reaches on each side of its center there.
+#[derive(Debug)]
+pub struct Violin {
+    outline: Vec<(f32, f32)>,
+    q1: f32,
+    median: f32,
+    q3: f32,
+    color: String,
+    violin_width: f32,
+    offset: f32,
+}
+
+impl Violin {
+    pub fn new(
+        outline: Vec<(f32, f32)>,
+        (q1, median, q3): (f32, f32, f32),
+        color: String,
+        violin_width: f32,
+        offset: f32,
+    ) -> Self {
+        Self {
+            outline,
+            q1,
+            median,
+            q3,
+            color,
+            violin_width,
+            offset,
+        }
+    }
+}
+
+impl DatumRepresentation for Violin {
+
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new()
+            .set("transform", format!("translate({},{})", self.offset, 0))
+            .set("class", "violin");
+
+        let center = self.violin_width / 2_f32;
+        let mut data = Data::new();
+
+        // Go along the right side of the outline and come back along the left one.
+        for (i, (y, half_width)) in self.outline.iter().enumerate() {
+            if i == 0 {
+                data = data.move_to((center + half_width, *y));
+            } else {
+                data = data.line_to((center + half_width, *y));
+            }
+        }
+        for (y, half_width) in self.outline.iter().rev() {
+            data = data.line_to((center - half_width, *y));
+        }
+
+        group.append(Path::new()
+            .set("fill", self.color.as_ref())
+            .set("stroke", self.color.as_ref())
+            .set("d", data.close()));
+
+        // A thick line over the interquartile range and a dot at the median, like a tiny box plot.
+        group.append(Line::new()
+            .set("x1", center)
+            .set("x2", center)
+            .set("y1", self.q1)
+            .set("y2", self.q3)
+            .set("stroke", "#333")
+            .set("stroke-width", 4));
+
+        group.append(Circle::new()
+            .set("cx", center)
+            .set("cy", self.median)
+            .set("r", 3)
+            .set("fill", "#fff"));
+
+        Ok(group)
+    }
+}
@@ -0,0 +1,28 @@
+use crate::axis::AxisPosition;
+
+/// Errors that can occur while building, rendering or saving a chart.
+#[derive(thiserror::Error, Debug)]
+pub enum ChartError {
+    #[error("please provide a scale for the {0} dimension before loading data")]
+    MissingScale(&'static str),
+    #[error("the {dimension} axis scale should be a {expected} scale")]
+    WrongScaleType { dimension: &'static str, expected: &'static str },
+    #[error("cannot add a label to the {0:?} axis without adding the axis first")]
+    MissingAxis(AxisPosition),
+    #[error("\"{0}\" isn't in the domain of the scale")]
+    NotInDomain(String),
+    #[error("\"{0}\" isn't one of the keys set on the view")]
+    UnknownKey(String),
+    #[error("the dataset is empty")]
+    EmptyData,
+    #[error("the color palette is empty")]
+    NoColors,
+    #[error("tick label \"{0}\" isn't a number, so it can't be formatted")]
+    InvalidTickLabel(String),
+    #[error("unable to rasterize the chart: {0}")]
+    Raster(String),
+    #[error("unable to save the chart as {0:?}, only svg and png are supported")]
+    UnsupportedFormat(String),
+    #[error("unable to write the chart: {0}")]
+    Io(#[from] std::io::Error),
+}
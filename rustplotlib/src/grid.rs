@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+use svg::node::element::{Group, Text};
+use svg::node::Text as TextNode;
+use svg::Node;
+use crate::chart::Chart;
+use crate::components::legend::LegendEntry;
+use crate::legend::Legend;
+use crate::ChartError;
+
+const TITLE_HEIGHT: isize = 36;
+const LEGEND_PADDING: isize = 10;
+
+/// A Grid places charts in rows and columns, filling each row from left to right.
+/// Every column is as wide as its widest chart and every row as tall as its tallest one.
+pub struct Grid {
+    columns: usize,
+    gap: isize,
+    title: String,
+    legend: bool,
+    charts: Vec<Chart>,
+}
+
+impl Grid {
+    /// Create a new empty grid with the given number of columns.
+    pub fn new(columns: usize) -> Self {
+        Self {
+            columns: columns.max(1),
+            gap: 20,
+            title: String::new(),
+            legend: false,
+            charts: Vec::new(),
+        }
+    }
+
+    /// Set the space between neighbouring charts.
+    pub fn set_gap(mut self, gap: isize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Add a title above the charts.
+    pub fn add_title(mut self, title: String) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Add a single legend above the charts with the entries of all of them.
+    /// Entries with the same label are only shown once, and unlabeled ones are left out.
+    pub fn add_legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+
+    /// Add a chart in the next free cell.
+    pub fn add_chart(mut self, chart: Chart) -> Self {
+        self.charts.push(chart);
+        self
+    }
+
+    /// Compute the width of each column.
+    fn get_column_widths(&self) -> Vec<isize> {
+        let mut widths = vec![0; self.columns.min(self.charts.len())];
+        for (i, chart) in self.charts.iter().enumerate() {
+            let column = i % self.columns;
+            widths[column] = widths[column].max(chart.get_chart_width());
+        }
+        widths
+    }
+
+    /// Compute the height of each row.
+    fn get_row_heights(&self) -> Vec<isize> {
+        self.charts.chunks(self.columns)
+            .map(|row| row.iter().map(|chart| chart.get_chart_height()).max().unwrap_or(0))
+            .collect()
+    }
+
+    /// Return the entries of the shared legend.
+    fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        let mut seen = HashSet::new();
+        self.charts.iter()
+            .flat_map(|chart| chart.get_legend_entries())
+            .filter(|entry| !entry.get_label().is_empty() && seen.insert(entry.get_label().to_string()))
+            .collect()
+    }
+
+    /// Return the legend shared by the charts, if the grid has one.
+    fn get_legend(&self) -> Option<Legend> {
+        if self.legend {
+            Some(Legend::new(self.get_legend_entries(), self.get_width() as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Compute how far down from the top of the grid the charts start.
+    fn get_header_height(&self) -> isize {
+        let title_height = if self.title.is_empty() { 0 } else { TITLE_HEIGHT };
+        let legend_height = match self.get_legend() {
+            Some(legend) if legend.get_height() > 0 => legend.get_height() as isize + LEGEND_PADDING,
+            _ => 0,
+        };
+        title_height + legend_height
+    }
+
+    /// Return the width of the whole grid.
+    pub fn get_width(&self) -> isize {
+        let widths = self.get_column_widths();
+        widths.iter().sum::<isize>() + self.gap * (widths.len().max(1) as isize - 1)
+    }
+
+    /// Return the height of the whole grid.
+    pub fn get_height(&self) -> isize {
+        let heights = self.get_row_heights();
+        self.get_header_height() + heights.iter().sum::<isize>() + self.gap * (heights.len().max(1) as isize - 1)
+    }
+
+    /// Generate the SVG for the grid and all of its charts.
+    pub fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new()
+            .set("class", "g-grid");
+
+        if !self.title.is_empty() {
+            group.append(Text::new()
+                .set("x", self.get_width() / 2)
+                .set("y", TITLE_HEIGHT / 2)
+                .set("dy", ".35em")
+                .set("fill", "#777")
+                .set("text-anchor", "middle")
+                .set("font-size", "20px")
+                .set("font-family", "sans-serif")
+                .add(TextNode::new(&self.title)));
+        }
+
+        if let Some(legend) = self.get_legend() {
+            let mut legend_group = legend.to_svg()?;
+            let y_offset = if self.title.is_empty() { 0 } else { TITLE_HEIGHT };
+            legend_group.assign("transform", format!("translate({},{})", 0, y_offset));
+            group.append(legend_group);
+        }
+
+        let widths = self.get_column_widths();
+        let heights = self.get_row_heights();
+        let mut y_offset = self.get_header_height();
+        for (row, charts) in self.charts.chunks(self.columns).enumerate() {
+            let mut x_offset = 0;
+            for (column, chart) in charts.iter().enumerate() {
+                let mut chart_group = chart.to_svg()?;
+                chart_group.assign("transform", format!("translate({},{})", x_offset, y_offset));
+                group.append(chart_group);
+                x_offset += widths[column] + self.gap;
+            }
+            y_offset += heights[row] + self.gap;
+        }
+
+        Ok(group)
+    }
+}
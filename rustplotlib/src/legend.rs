@@ -2,6 +2,10 @@ use svg::node::element::Group;
 use svg::Node;
 use crate::components::legend::LegendEntry;
 
+const GAP_BETWEEN_LEGEND_ENTRIES: usize = 10;
+const LEGEND_ROW_HEIGHT: usize = 20;
+use crate::ChartError;
+
 pub(crate) struct Legend {
     width: usize,
     entries: Vec<LegendEntry>,
@@ -16,14 +20,22 @@ impl Legend {
         }
     }
 
-    pub fn to_svg(&self) -> Result<Group, String> {
+    /// Return the height the legend takes up once its entries are wrapped into rows.
+    pub fn get_height(&self) -> usize {
+        let max_entry_length = match self.entries.iter().map(|entry| entry.get_width()).max() {
+            None => return 0,
+            Some(len) => len,
+        };
+        let entries_per_row = ((self.width + GAP_BETWEEN_LEGEND_ENTRIES) / (max_entry_length + GAP_BETWEEN_LEGEND_ENTRIES)).max(1);
+        self.entries.len().div_ceil(entries_per_row) * LEGEND_ROW_HEIGHT
+    }
+
+    pub fn to_svg(&self) -> Result<Group, ChartError> {
         let mut group = Group::new().set("class", "g-legend");
         let max_entry_length = match self.entries.iter().map(|entry| entry.get_width()).max() {
             None => return Ok(group),
             Some(len) => len,
         };
-        let gap_between_legend_entries = 10;
-        let legend_row_height = 20;
         let mut current_row_offset = 0;
         let mut acc_row_width = 0;
 
@@ -34,10 +46,10 @@ impl Legend {
             }
 
             let mut entry_group = entry.to_svg()?;
-            entry_group.assign("transform", format!("translate({},{})", acc_row_width, current_row_offset * legend_row_height));
+            entry_group.assign("transform", format!("translate({},{})", acc_row_width, current_row_offset * LEGEND_ROW_HEIGHT));
             group.append(entry_group);
 
-            acc_row_width += max_entry_length + gap_between_legend_entries;
+            acc_row_width += max_entry_length + GAP_BETWEEN_LEGEND_ENTRIES;
         }
 
         Ok(group)
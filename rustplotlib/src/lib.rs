@@ -8,6 +8,10 @@
 //!
 //! 1. Bar Chart (horizontal and vertical)
 //! 2. Stacked Bar Chart (horizontal and vertical)
+//! 3. Box Plot and Violin Plot
+//! 4. Histogram (overlaid and stacked)
+//! 5. Heatmap
+//! 6. Pie and Donut Chart
 //!
 //! ## Abstraction Layers
 //!
@@ -23,6 +27,8 @@
 //! TODO represent the structure visually
 
 mod chart;
+mod grid;
+mod page;
 // mod view;
 mod scales;
 mod views;
@@ -30,8 +36,13 @@ mod components;
 mod colors;
 mod axis;
 mod legend;
+mod error;
+mod raster;
+mod stats;
 
 pub use crate::chart::Chart;
+pub use crate::grid::Grid;
+pub use crate::page::Page;
 pub use crate::scales::band::ScaleBand;
 pub use crate::scales::linear::ScaleLinear;
 pub use crate::scales::Scale;
@@ -40,12 +51,21 @@ pub use crate::views::horizontal_bar::HorizontalBarView;
 pub use crate::views::scatter::ScatterView;
 pub use crate::views::line::LineSeriesView;
 pub use crate::views::area::AreaSeriesView;
-pub use crate::views::datum::{BarDatum, PointDatum};
+pub use crate::views::box_plot::BoxPlotView;
+pub use crate::views::violin::ViolinView;
+pub use crate::views::histogram::{Binning, HistogramMode, HistogramView};
+pub use crate::views::heatmap::{HeatmapDimension, HeatmapView};
+pub use crate::views::pie::{DonutView, PieView};
+pub use crate::views::datum::{BarDatum, HeatmapDatum, HistogramDatum, PointDatum, SampleDatum};
 pub use crate::axis::{Axis, AxisPosition};
-pub use crate::components::bar::BarLabelPosition;
+pub use crate::components::bar::{BarLabelPosition, BarMode};
+pub use crate::components::pie::SliceLabelPosition;
 pub use crate::components::line::LineSeries;
 pub use crate::components::scatter::{MarkerType, PointLabelPosition};
 pub use crate::colors::Color;
+pub use crate::colors::scale::ColorScale;
+pub use crate::error::ChartError;
+pub use crate::raster::svg_to_png;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,127 @@
+use std::path::Path;
+use svg::node::element::{Group, Text};
+use svg::node::Text as TextNode;
+use svg::Node;
+use crate::chart::save_document;
+use crate::grid::Grid;
+use crate::raster::svg_to_png;
+use crate::ChartError;
+
+const TITLE_HEIGHT: isize = 50;
+
+/// A Page is the biggest entity that can be saved. It stacks grids from top to bottom
+/// under a single title, and is output as one document.
+pub struct Page {
+    margin: isize,
+    gap: isize,
+    title: String,
+    grids: Vec<Grid>,
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Page {
+    /// Create a new empty page.
+    pub fn new() -> Self {
+        Self {
+            margin: 20,
+            gap: 30,
+            title: String::new(),
+            grids: Vec::new(),
+        }
+    }
+
+    /// Set the space around the edges of the page.
+    pub fn set_margin(mut self, margin: isize) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Set the space between neighbouring grids.
+    pub fn set_gap(mut self, gap: isize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Add a title at the top of the page.
+    pub fn add_title(mut self, title: String) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Add a grid below the ones already on the page.
+    pub fn add_grid(mut self, grid: Grid) -> Self {
+        self.grids.push(grid);
+        self
+    }
+
+    fn get_title_height(&self) -> isize {
+        if self.title.is_empty() { 0 } else { TITLE_HEIGHT }
+    }
+
+    /// Return the width of the whole page.
+    pub fn get_width(&self) -> isize {
+        self.grids.iter().map(|grid| grid.get_width()).max().unwrap_or(0) + 2 * self.margin
+    }
+
+    /// Return the height of the whole page.
+    pub fn get_height(&self) -> isize {
+        let grids_height = self.grids.iter().map(|grid| grid.get_height()).sum::<isize>()
+            + self.gap * (self.grids.len().max(1) as isize - 1);
+        self.get_title_height() + grids_height + 2 * self.margin
+    }
+
+    /// Generate the SVG for the page and everything on it.
+    pub fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new()
+            .set("class", "g-page");
+
+        if !self.title.is_empty() {
+            group.append(Text::new()
+                .set("x", self.get_width() / 2)
+                .set("y", self.margin + TITLE_HEIGHT / 2)
+                .set("dy", ".35em")
+                .set("fill", "#555")
+                .set("text-anchor", "middle")
+                .set("font-size", "28px")
+                .set("font-family", "sans-serif")
+                .add(TextNode::new(&self.title)));
+        }
+
+        let mut y_offset = self.margin + self.get_title_height();
+        for grid in self.grids.iter() {
+            let mut grid_group = grid.to_svg()?;
+            grid_group.assign("transform", format!("translate({},{})", self.margin, y_offset));
+            group.append(grid_group);
+            y_offset += grid.get_height() + self.gap;
+        }
+
+        Ok(group)
+    }
+
+    /// Generate a standalone SVG document for the page, sized to the page.
+    pub fn to_document(&self) -> Result<svg::Document, ChartError> {
+        let (width, height) = (self.get_width(), self.get_height());
+        Ok(svg::Document::new()
+            .set("width", width)
+            .set("height", height)
+            .set("viewBox", (0, 0, width, height))
+            .add(self.to_svg()?))
+    }
+
+    /// Rasterize the page to PNG bytes.
+    pub fn to_png(&self) -> Result<Vec<u8>, ChartError> {
+        svg_to_png(&self.to_document()?.to_string())
+    }
+
+    /// Save the page to a file, as SVG or PNG depending on the extension.
+    pub fn save<P>(self, path: P) -> Result<(), ChartError> where
+        P: AsRef<Path>
+    {
+        save_document(&self.to_document()?, path)
+    }
+}
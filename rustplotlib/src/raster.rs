@@ -0,0 +1,40 @@
+use std::sync::{Arc, OnceLock};
+use resvg::{tiny_skia, usvg};
+use resvg::usvg::fontdb;
+use crate::ChartError;
+
+/// The font every chart is rasterized with, bundled so the output doesn't depend on
+/// which fonts happen to be installed.
+const FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSans.ttf");
+const FONT_FAMILY: &str = "DejaVu Sans";
+
+/// Load the bundled font once and share it between renders.
+fn font_database() -> Arc<fontdb::Database> {
+    static FONTS: OnceLock<Arc<fontdb::Database>> = OnceLock::new();
+    FONTS.get_or_init(|| {
+        let mut database = fontdb::Database::new();
+        database.load_font_data(FONT.to_vec());
+        database.set_sans_serif_family(FONT_FAMILY);
+        Arc::new(database)
+    }).clone()
+}
+
+/// Rasterize an SVG document to PNG bytes, on a white background and at the
+/// document's own size.
+pub fn svg_to_png(svg: &str) -> Result<Vec<u8>, ChartError> {
+    let options = usvg::Options {
+        font_family: FONT_FAMILY.to_string(),
+        fontdb: font_database(),
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options)
+        .map_err(|e| ChartError::Raster(e.to_string()))?;
+
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| ChartError::Raster(format!("unable to create a {}x{} image", size.width(), size.height())))?;
+    pixmap.fill(tiny_skia::Color::WHITE);
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    pixmap.encode_png().map_err(|e| ChartError::Raster(e.to_string()))
+}
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use crate::scales::{Scale, ScaleType};
 
 /// The scale to represent categorical data.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ScaleBand {
     /// The domain limits of the dataset that the scale is going to represent.
     domain: Vec<String>,
@@ -169,6 +169,11 @@ impl Scale<String> for ScaleBand {
         Some(self.bandwidth)
     }
 
+    /// Check whether the value is one of the categories of the scale.
+    fn contains(&self, domain: &String) -> bool {
+        self.index.contains_key(domain)
+    }
+
     /// Get the start range value.
     fn range_start(&self) -> f32 {
         self.range[0] as f32
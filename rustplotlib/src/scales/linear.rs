@@ -2,7 +2,7 @@ use std::cmp::{max, Ordering};
 use crate::scales::{Scale, ScaleType};
 
 /// The scale to represent categorical data.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ScaleLinear {
     /// The domain limits of the dataset that the scale is going to represent.
     domain: Vec<f32>,
@@ -9,7 +9,7 @@ pub enum ScaleType {
 }
 
 /// The Scale trait defines common operations on all scales.
-pub trait Scale<T> {
+pub trait Scale<T>: Send + Sync {
     /// Get the type of the scale.
     fn get_type(&self) -> ScaleType;
 
@@ -30,6 +30,11 @@ pub trait Scale<T> {
         self.range_start() > self.range_end()
     }
 
+    /// Check whether the value can be placed by the scale. Continuous scales take any value.
+    fn contains(&self, _domain: &T) -> bool {
+        true
+    }
+
     /// Get the list of ticks that represent the scale on a chart axis.
     fn get_ticks(&self) -> Vec<T>;
 }
\ No newline at end of file
@@ -0,0 +1,234 @@
+/// Summary statistics of a set of samples, as drawn by a box plot.
+#[derive(Debug)]
+pub(crate) struct Summary {
+    pub(crate) q1: f32,
+    pub(crate) median: f32,
+    pub(crate) q3: f32,
+    pub(crate) low_whisker: f32,
+    pub(crate) high_whisker: f32,
+    pub(crate) outliers: Vec<f32>,
+}
+
+impl Summary {
+    /// Compute the summary of the samples. The whiskers reach the furthest samples that are
+    /// within `whisker_range` interquartile ranges of the box, everything beyond is an outlier.
+    /// Return `None` when there are no samples.
+    pub(crate) fn new(samples: &[f32], whisker_range: f32) -> Option<Self> {
+        let sorted = sorted(samples);
+        if sorted.is_empty() {
+            return None;
+        }
+
+        let q1 = quantile(&sorted, 0.25);
+        let median = quantile(&sorted, 0.5);
+        let q3 = quantile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (low_fence, high_fence) = (q1 - whisker_range * iqr, q3 + whisker_range * iqr);
+
+        let mut inside = sorted.iter().filter(|value| **value >= low_fence && **value <= high_fence);
+        let low_whisker = inside.clone().next().copied().unwrap_or(q1);
+        let high_whisker = inside.next_back().copied().unwrap_or(q3);
+        let outliers = sorted.iter().filter(|value| **value < low_fence || **value > high_fence).copied().collect();
+
+        Some(Self { q1, median, q3, low_whisker, high_whisker, outliers })
+    }
+}
+
+/// Return the samples in ascending order, leaving out the ones that aren't numbers.
+pub(crate) fn sorted(samples: &[f32]) -> Vec<f32> {
+    let mut sorted: Vec<f32> = samples.iter().copied().filter(|value| !value.is_nan()).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    sorted
+}
+
+/// Compute the `p` quantile of sorted samples, interpolating linearly between
+/// the closest ranks. The samples must not be empty.
+pub(crate) fn quantile(sorted: &[f32], p: f32) -> f32 {
+    let position = p * (sorted.len() - 1) as f32;
+    let lower = position.floor() as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    sorted[lower] + (position - lower as f32) * (sorted[upper] - sorted[lower])
+}
+
+/// Compute the sample standard deviation.
+pub(crate) fn standard_deviation(samples: &[f32]) -> f32 {
+    if samples.len() < 2 {
+        return 0_f32;
+    }
+    let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+    let variance = samples.iter().map(|value| (value - mean).powi(2)).sum::<f32>() / (samples.len() - 1) as f32;
+    variance.sqrt()
+}
+
+/// Estimate a kernel bandwidth for the samples with Silverman's rule of thumb.
+/// Samples that are all the same fall back to a bandwidth of 1.
+pub(crate) fn silverman_bandwidth(sorted: &[f32]) -> f32 {
+    let deviation = standard_deviation(sorted);
+    let spread = match (quantile(sorted, 0.75) - quantile(sorted, 0.25)) / 1.34 {
+        iqr if iqr > 0_f32 => deviation.min(iqr),
+        _ => deviation,
+    };
+
+    if spread > 0_f32 {
+        0.9 * spread * (sorted.len() as f32).powf(-0.2)
+    } else {
+        1_f32
+    }
+}
+
+/// Estimate the density of the samples with a gaussian kernel, at `resolution` evenly spaced
+/// points between the smallest and the biggest sample. Return (value, density) pairs.
+pub(crate) fn kernel_density(samples: &[f32], bandwidth: Option<f32>, resolution: usize) -> Vec<(f32, f32)> {
+    let sorted = sorted(samples);
+    if sorted.is_empty() {
+        return Vec::new();
+    }
+
+    let bandwidth = bandwidth.filter(|bandwidth| *bandwidth > 0_f32).unwrap_or_else(|| silverman_bandwidth(&sorted));
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let resolution = resolution.max(2);
+    let normalization = sorted.len() as f32 * bandwidth * (2_f32 * std::f32::consts::PI).sqrt();
+
+    (0..resolution)
+        .map(|i| {
+            let value = min + (max - min) * i as f32 / (resolution - 1) as f32;
+            let density = sorted.iter()
+                .map(|sample| (-0.5 * ((value - sample) / bandwidth).powi(2)).exp())
+                .sum::<f32>() / normalization;
+            (value, density)
+        })
+        .collect()
+}
+
+/// Pick the number of histogram bins for `count` samples with Sturges' rule.
+pub(crate) fn sturges_bin_count(count: usize) -> usize {
+    (count.max(1) as f32).log2().ceil() as usize + 1
+}
+
+/// Pick the width of histogram bins for sorted samples with the Freedman-Diaconis rule.
+/// Return `None` when the interquartile range is empty and the rule can't be used.
+pub(crate) fn freedman_diaconis_width(sorted: &[f32]) -> Option<f32> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let iqr = quantile(sorted, 0.75) - quantile(sorted, 0.25);
+    let width = 2_f32 * iqr * (sorted.len() as f32).powf(-1_f32 / 3_f32);
+    if width > 0_f32 { Some(width) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorted_drops_values_that_arent_numbers() {
+        assert_eq!(sorted(&[3_f32, f32::NAN, 1_f32, 2_f32]), vec![1_f32, 2_f32, 3_f32]);
+        assert!(sorted(&[]).is_empty());
+    }
+
+    #[test]
+    fn quantile_interpolates_between_ranks() {
+        let values = [1_f32, 2_f32, 3_f32, 4_f32];
+        assert_eq!(quantile(&values, 0_f32), 1_f32);
+        assert_eq!(quantile(&values, 0.5), 2.5);
+        assert_eq!(quantile(&values, 0.25), 1.75);
+        assert_eq!(quantile(&values, 1_f32), 4_f32);
+    }
+
+    #[test]
+    fn quantile_of_a_single_value_is_that_value() {
+        assert_eq!(quantile(&[7_f32], 0_f32), 7_f32);
+        assert_eq!(quantile(&[7_f32], 0.5), 7_f32);
+        assert_eq!(quantile(&[7_f32], 1_f32), 7_f32);
+    }
+
+    #[test]
+    fn summary_of_nothing_is_none() {
+        assert!(Summary::new(&[], 1.5).is_none());
+        assert!(Summary::new(&[f32::NAN], 1.5).is_none());
+    }
+
+    #[test]
+    fn summary_of_equal_values_collapses() {
+        let summary = Summary::new(&[4_f32; 5], 1.5).unwrap();
+        assert_eq!((summary.q1, summary.median, summary.q3), (4_f32, 4_f32, 4_f32));
+        assert_eq!((summary.low_whisker, summary.high_whisker), (4_f32, 4_f32));
+        assert!(summary.outliers.is_empty());
+    }
+
+    #[test]
+    fn summary_separates_outliers() {
+        let summary = Summary::new(&[1_f32, 2_f32, 3_f32, 4_f32, 5_f32, 100_f32], 1.5).unwrap();
+        assert_eq!(summary.median, 3.5);
+        assert_eq!(summary.low_whisker, 1_f32);
+        assert_eq!(summary.high_whisker, 5_f32);
+        assert_eq!(summary.outliers, vec![100_f32]);
+    }
+
+    #[test]
+    fn whiskers_reach_the_extremes_without_outliers() {
+        let summary = Summary::new(&[10_f32, 1_f32, 5_f32], 1.5).unwrap();
+        assert_eq!((summary.low_whisker, summary.high_whisker), (1_f32, 10_f32));
+        assert!(summary.outliers.is_empty());
+    }
+
+    #[test]
+    fn standard_deviation_needs_two_samples() {
+        assert_eq!(standard_deviation(&[]), 0_f32);
+        assert_eq!(standard_deviation(&[3_f32]), 0_f32);
+        assert_eq!(standard_deviation(&[2_f32, 4_f32, 4_f32, 4_f32, 5_f32, 5_f32, 7_f32, 9_f32]), (32_f32 / 7_f32).sqrt());
+    }
+
+    #[test]
+    fn equal_samples_fall_back_to_a_unit_bandwidth() {
+        assert_eq!(silverman_bandwidth(&[2_f32, 2_f32, 2_f32]), 1_f32);
+        assert_eq!(silverman_bandwidth(&[2_f32]), 1_f32);
+    }
+
+    #[test]
+    fn kernel_density_of_nothing_is_empty() {
+        assert!(kernel_density(&[], None, 10).is_empty());
+    }
+
+    #[test]
+    fn kernel_density_spans_the_samples() {
+        let density = kernel_density(&[0_f32, 1_f32, 2_f32, 10_f32], Some(1_f32), 11);
+        assert_eq!(density.len(), 11);
+        assert_eq!(density.first().unwrap().0, 0_f32);
+        assert_eq!(density.last().unwrap().0, 10_f32);
+        // the samples bunch up at the low end
+        assert!(density[1].1 > density[5].1);
+    }
+
+    #[test]
+    fn kernel_density_of_a_single_sample_peaks_on_it() {
+        let density = kernel_density(&[3_f32], Some(1_f32), 1);
+        // the resolution is at least 2, so both ends land on the sample
+        assert_eq!(density.len(), 2);
+        let peak = 1_f32 / (2_f32 * std::f32::consts::PI).sqrt();
+        assert!(density.iter().all(|(value, d)| *value == 3_f32 && (d - peak).abs() < 1e-6));
+    }
+
+    #[test]
+    fn a_bad_bandwidth_is_estimated_instead() {
+        assert_eq!(kernel_density(&[1_f32, 2_f32, 4_f32], Some(0_f32), 5), kernel_density(&[1_f32, 2_f32, 4_f32], None, 5));
+    }
+
+    #[test]
+    fn sturges_counts_bins_from_the_number_of_samples() {
+        assert_eq!(sturges_bin_count(0), 1);
+        assert_eq!(sturges_bin_count(1), 1);
+        assert_eq!(sturges_bin_count(8), 4);
+        assert_eq!(sturges_bin_count(9), 5);
+    }
+
+    #[test]
+    fn freedman_diaconis_needs_a_spread() {
+        assert_eq!(freedman_diaconis_width(&[]), None);
+        assert_eq!(freedman_diaconis_width(&[5_f32]), None);
+        assert_eq!(freedman_diaconis_width(&[5_f32, 5_f32, 5_f32, 5_f32]), None);
+        // the interquartile range of 1 to 9 is 4, with 9 samples
+        let values: Vec<f32> = (1..=9).map(|v| v as f32).collect();
+        assert_eq!(freedman_diaconis_width(&values), Some(8_f32 / 9_f32.powf(1_f32 / 3_f32)));
+    }
+}
@@ -4,25 +4,26 @@ use crate::components::scatter::{ScatterPoint, MarkerType, PointLabelPosition};
 use crate::colors::Color;
 use crate::Scale;
 use crate::views::datum::PointDatum;
-use crate::views::View;
+use crate::views::{View, check_points};
 use crate::components::DatumRepresentation;
 use std::fmt::Display;
 use crate::components::legend::{LegendEntry, LegendMarkerType};
 use crate::components::area::AreaSeries;
+use crate::ChartError;
 
 /// A View that represents data as a scatter plot.
-pub struct AreaSeriesView<'a, T: Display + Clone, U: Display + Clone> {
+pub struct AreaSeriesView<T: Display + Clone, U: Display + Clone> {
     labels_visible: bool,
     label_position: PointLabelPosition,
     marker_type: MarkerType,
     entries: Vec<AreaSeries<T, U>>,
     colors: Vec<Color>,
-    x_scale: Option<&'a dyn Scale<T>>,
-    y_scale: Option<&'a dyn Scale<U>>,
+    x_scale: Option<Box<dyn Scale<T>>>,
+    y_scale: Option<Box<dyn Scale<U>>>,
     custom_data_label: String,
 }
 
-impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
+impl<T: Display + Clone, U: Display + Clone> AreaSeriesView<T, U> {
     /// Create a new empty instance of the view.
     pub fn new() -> Self {
         Self {
@@ -38,14 +39,14 @@ impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
     }
 
     /// Set the scale for the X dimension.
-    pub fn set_x_scale(mut self, scale: &'a impl Scale<T>) -> Self {
-        self.x_scale = Some(scale);
+    pub fn set_x_scale(mut self, scale: &(impl Scale<T> + Clone + 'static)) -> Self {
+        self.x_scale = Some(Box::new(scale.clone()));
         self
     }
 
     /// Set the scale for the Y dimension.
-    pub fn set_y_scale(mut self, scale: &'a impl Scale<U>) -> Self {
-        self.y_scale = Some(scale);
+    pub fn set_y_scale(mut self, scale: &(impl Scale<U> + Clone + 'static)) -> Self {
+        self.y_scale = Some(Box::new(scale.clone()));
         self
     }
 
@@ -83,49 +84,50 @@ impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
     }
 
     /// Load and process a dataset of BarDatum points.
-    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, String> {
-        match self.x_scale {
-            Some(_) => {},
-            _ => return Err("Please provide a scale for the X dimension before loading data".to_string()),
-        }
-        match self.y_scale {
-            Some(_) => {},
-            _ => return Err("Please provide a scale for the Y dimension before loading data".to_string()),
+    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, ChartError> {
+        let x_scale = self.x_scale.as_deref().ok_or(ChartError::MissingScale("X"))?;
+        let y_scale = self.y_scale.as_deref().ok_or(ChartError::MissingScale("Y"))?;
+
+        if self.colors.is_empty() {
+            return Err(ChartError::NoColors);
         }
+        check_points(data, x_scale, y_scale)?;
+        let (first, last) = match (data.first(), data.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(ChartError::EmptyData),
+        };
 
         // Compute corresponding offsets to apply in case there is a non-zero bandwidth.
         let y_bandwidth_offset = {
-            if self.y_scale.unwrap().is_range_reversed() {
-                -self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
+            if y_scale.is_range_reversed() {
+                -y_scale.bandwidth().unwrap() / 2_f32
             } else {
-                self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
+                y_scale.bandwidth().unwrap() / 2_f32
             }
         };
         let x_bandwidth_offset = {
-            if self.x_scale.unwrap().is_range_reversed() {
-                -self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
+            if x_scale.is_range_reversed() {
+                -x_scale.bandwidth().unwrap() / 2_f32
             } else {
-                self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
+                x_scale.bandwidth().unwrap() / 2_f32
             }
         };
 
         let mut points = data.iter().map(|datum| {
-            let scaled_x = self.x_scale.unwrap().scale(&datum.get_x());
-            let scaled_y = self.y_scale.unwrap().scale(&datum.get_y());
+            let scaled_x = x_scale.scale(&datum.get_x());
+            let scaled_y = y_scale.scale(&datum.get_y());
             ScatterPoint::new(scaled_x + x_bandwidth_offset, scaled_y + y_bandwidth_offset, self.marker_type, 5, datum.get_x(), datum.get_y(), self.label_position, self.labels_visible, true, self.colors[0].as_hex())
         }).collect::<Vec<ScatterPoint<T, U>>>();
 
         let y_origin = {
-            if self.y_scale.unwrap().is_range_reversed() {
-                self.y_scale.unwrap().range_start()
+            if y_scale.is_range_reversed() {
+                y_scale.range_start()
             } else {
-                self.y_scale.unwrap().range_end()
+                y_scale.range_end()
             }
         };
-        let first = data.first().unwrap();
-        let last = data.last().unwrap();
-        points.push(ScatterPoint::new(self.x_scale.unwrap().scale(&last.get_x()) + x_bandwidth_offset, y_origin, self.marker_type, 5, data[0].get_x(), data[0].get_y(), self.label_position, false, false, "#fff".to_string()));
-        points.push(ScatterPoint::new(self.x_scale.unwrap().scale(&first.get_x()) + x_bandwidth_offset, y_origin, self.marker_type, 5, data[0].get_x(), data[0].get_y(), self.label_position, false, false, "#fff".to_string()));
+        points.push(ScatterPoint::new(x_scale.scale(&last.get_x()) + x_bandwidth_offset, y_origin, self.marker_type, 5, data[0].get_x(), data[0].get_y(), self.label_position, false, false, "#fff".to_string()));
+        points.push(ScatterPoint::new(x_scale.scale(&first.get_x()) + x_bandwidth_offset, y_origin, self.marker_type, 5, data[0].get_x(), data[0].get_y(), self.label_position, false, false, "#fff".to_string()));
 
         self.entries.push(AreaSeries::new(points, self.colors[0].as_hex()));
 
@@ -133,9 +135,9 @@ impl<'a, T: Display + Clone, U: Display + Clone> AreaSeriesView<'a, T, U> {
     }
 }
 
-impl<'a, T: Display + Clone, U: Display + Clone> View<'a> for AreaSeriesView<'a, T, U> {
+impl<T: Display + Send + Sync + Clone, U: Display + Send + Sync + Clone> View for AreaSeriesView<T, U> {
     /// Generate the SVG representation of the view.
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
         let mut group = Group::new();
 
         for entry in self.entries.iter() {
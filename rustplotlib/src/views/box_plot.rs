@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use svg::node::Node;
+use svg::node::element::Group;
+use crate::components::box_plot::BoxPlot;
+use crate::colors::Color;
+use crate::{Scale, SampleDatum};
+use crate::scales::ScaleType;
+use crate::components::DatumRepresentation;
+use crate::views::{View, extract_sample_keys, group_samples};
+use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::stats::Summary;
+use crate::ChartError;
+
+/// A View that summarizes the distribution of the samples in each category as a box
+/// spanning the interquartile range, with whiskers and outliers.
+pub struct BoxPlotView {
+    entries: Vec<BoxPlot>,
+    keys: Vec<String>,
+    colors: Vec<Color>,
+    color_map: HashMap<String, String>,
+    x_scale: Option<Box<dyn Scale<String>>>,
+    y_scale: Option<Box<dyn Scale<f32>>>,
+    custom_data_label: String,
+    padding: f32,
+    whisker_range: f32,
+}
+
+impl Default for BoxPlotView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoxPlotView {
+    /// Create a new empty instance of the view.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            keys: Vec::new(),
+            colors: Color::color_scheme_10(),
+            color_map: HashMap::new(),
+            x_scale: None,
+            y_scale: None,
+            custom_data_label: String::new(),
+            padding: 0.3,
+            whisker_range: 1.5,
+        }
+    }
+
+    /// Set the scale for the X dimension.
+    pub fn set_x_scale(mut self, scale: &(impl Scale<String> + Clone + 'static)) -> Self {
+        self.x_scale = Some(Box::new(scale.clone()));
+        self
+    }
+
+    /// Set the scale for the Y dimension.
+    pub fn set_y_scale(mut self, scale: &(impl Scale<f32> + Clone + 'static)) -> Self {
+        self.y_scale = Some(Box::new(scale.clone()));
+        self
+    }
+
+    /// Set the keys of the distributions shown side by side within each category.
+    pub fn set_keys(mut self, keys: Vec<String>) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Set the color palette of the view.
+    pub fn set_colors(mut self, colors: Vec<Color>) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Set custom label for the dataset.
+    /// This will work when the dataset represents only a single
+    /// type of data (i.e. there are no different "keys" by which to
+    /// differentiate data), otherwise, this will have no effect.
+    pub fn set_custom_data_label(mut self, label: String) -> Self {
+        self.custom_data_label = label;
+        self
+    }
+
+    /// Set the fraction of each distribution's slot that is left empty around its box.
+    pub fn set_padding(mut self, padding: f32) -> Self {
+        self.padding = padding.clamp(0_f32, 1_f32);
+        self
+    }
+
+    /// Set how many interquartile ranges the whiskers may reach beyond the box.
+    /// Samples further away than that are drawn as outliers. Defaults to 1.5.
+    pub fn set_whisker_range(mut self, whisker_range: f32) -> Self {
+        self.whisker_range = whisker_range.max(0_f32);
+        self
+    }
+
+    /// Load and process a dataset of SampleDatum points.
+    pub fn load_data(mut self, data: &[impl SampleDatum]) -> Result<Self, ChartError> {
+        let x_scale = match self.x_scale.as_deref() {
+            Some(scale) if scale.get_type() == ScaleType::Band => scale,
+            _ => return Err(ChartError::WrongScaleType { dimension: "X", expected: "Band" }),
+        };
+        let y_scale = match self.y_scale.as_deref() {
+            Some(scale) if scale.get_type() == ScaleType::Linear => scale,
+            _ => return Err(ChartError::WrongScaleType { dimension: "Y", expected: "Linear" }),
+        };
+
+        if self.colors.is_empty() {
+            return Err(ChartError::NoColors);
+        }
+
+        // If no keys were explicitly provided, extract the keys from the data.
+        if self.keys.is_empty() {
+            self.keys = extract_sample_keys(data);
+        }
+
+        for (i, key) in self.keys.iter().enumerate() {
+            self.color_map.insert(key.clone(), self.colors[i % self.colors.len()].as_hex());
+        }
+
+        // Every key gets its own slot within the band of a category.
+        let slot = x_scale.bandwidth().unwrap() / self.keys.len().max(1) as f32;
+        let box_width = slot * (1_f32 - self.padding);
+
+        for (category, index, samples) in group_samples(data, &self.keys, x_scale)? {
+            let summary = match Summary::new(&samples, self.whisker_range) {
+                Some(summary) => summary,
+                None => continue,
+            };
+
+            let offset = x_scale.scale(&category) + index as f32 * slot + (slot - box_width) / 2_f32;
+            let box_plot = BoxPlot::new(
+                (y_scale.scale(&summary.q1), y_scale.scale(&summary.median), y_scale.scale(&summary.q3)),
+                (y_scale.scale(&summary.low_whisker), y_scale.scale(&summary.high_whisker)),
+                summary.outliers.iter().map(|outlier| y_scale.scale(outlier)).collect(),
+                self.color_map.get(&self.keys[index]).unwrap().clone(),
+                box_width,
+                offset,
+            );
+            self.entries.push(box_plot);
+        }
+
+        Ok(self)
+    }
+}
+
+impl View for BoxPlotView {
+    /// Generate the SVG representation of the view.
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new();
+
+        for entry in self.entries.iter() {
+            let child_svg = entry.to_svg()?;
+            group.append(child_svg);
+        }
+
+        Ok(group)
+    }
+
+    /// Return the legend entries that this view represents.
+    fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        let mut entries = Vec::new();
+
+        // If there is a single key and it is an empty string (meaning
+        // the dataset consists only of categories and values), return
+        // the custom data label.
+        if self.keys.len() == 1 && self.keys[0].is_empty() {
+            entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(&self.keys[0]).unwrap().clone(), String::from("none"), self.custom_data_label.clone()));
+        } else {
+            for key in self.keys.iter() {
+                entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(key).unwrap().clone(), String::from("none"), key.clone()));
+            }
+        }
+
+        entries
+    }
+}
@@ -279,3 +279,248 @@ impl PointDatum<String, f32> for (String, isize) {
         String::new()
     }
 }
+
+/// A trait that defines interaction with a single sample of a distribution, as
+/// used in box plots and violin plots. All samples that share a category and a key
+/// make up one distribution.
+pub trait SampleDatum {
+    /// Return the category of the sample.
+    fn get_category(&self) -> String;
+
+    /// Return the value of the sample.
+    fn get_value(&self) -> f32;
+
+    /// Return the key of the sample. This is optional when there is a single
+    /// distribution per category (just return an empty string), but is required
+    /// to show several distributions side by side within a category.
+    fn get_key(&self) -> String;
+}
+
+impl SampleDatum for (String, f32) {
+    fn get_category(&self) -> String {
+        self.0.clone()
+    }
+
+    fn get_value(&self) -> f32 {
+        self.1
+    }
+
+    fn get_key(&self) -> String {
+        String::new()
+    }
+}
+
+impl SampleDatum for (&str, f32) {
+    fn get_category(&self) -> String {
+        String::from(self.0)
+    }
+
+    fn get_value(&self) -> f32 {
+        self.1
+    }
+
+    fn get_key(&self) -> String {
+        String::new()
+    }
+}
+
+impl SampleDatum for (&str, i32) {
+    fn get_category(&self) -> String {
+        String::from(self.0)
+    }
+
+    fn get_value(&self) -> f32 {
+        self.1 as f32
+    }
+
+    fn get_key(&self) -> String {
+        String::new()
+    }
+}
+
+impl SampleDatum for (String, f32, String) {
+    fn get_category(&self) -> String {
+        self.0.clone()
+    }
+
+    fn get_value(&self) -> f32 {
+        self.1
+    }
+
+    fn get_key(&self) -> String {
+        self.2.clone()
+    }
+}
+
+impl SampleDatum for (&str, f32, &str) {
+    fn get_category(&self) -> String {
+        String::from(self.0)
+    }
+
+    fn get_value(&self) -> f32 {
+        self.1
+    }
+
+    fn get_key(&self) -> String {
+        String::from(self.2)
+    }
+}
+
+impl SampleDatum for (&str, i32, &str) {
+    fn get_category(&self) -> String {
+        String::from(self.0)
+    }
+
+    fn get_value(&self) -> f32 {
+        self.1 as f32
+    }
+
+    fn get_key(&self) -> String {
+        String::from(self.2)
+    }
+}
+
+/// A trait that defines interaction with a single value counted in a histogram.
+pub trait HistogramDatum {
+    /// Return the value of the datum.
+    fn get_value(&self) -> f32;
+
+    /// Return the key of the datum. This is optional in a histogram of a single
+    /// set of values (just return an empty string), but is required to overlay
+    /// or stack histograms of several sets of values.
+    fn get_key(&self) -> String;
+}
+
+impl HistogramDatum for f32 {
+    fn get_value(&self) -> f32 {
+        *self
+    }
+
+    fn get_key(&self) -> String {
+        String::new()
+    }
+}
+
+impl HistogramDatum for i32 {
+    fn get_value(&self) -> f32 {
+        *self as f32
+    }
+
+    fn get_key(&self) -> String {
+        String::new()
+    }
+}
+
+impl HistogramDatum for (f32, &str) {
+    fn get_value(&self) -> f32 {
+        self.0
+    }
+
+    fn get_key(&self) -> String {
+        String::from(self.1)
+    }
+}
+
+impl HistogramDatum for (f32, String) {
+    fn get_value(&self) -> f32 {
+        self.0
+    }
+
+    fn get_key(&self) -> String {
+        self.1.clone()
+    }
+}
+
+impl HistogramDatum for (i32, &str) {
+    fn get_value(&self) -> f32 {
+        self.0 as f32
+    }
+
+    fn get_key(&self) -> String {
+        String::from(self.1)
+    }
+}
+
+/// A trait that defines interaction with a value placed in a heatmap. Values that
+/// fall into the same cell are added up.
+pub trait HeatmapDatum<T, U> {
+    /// Return the X value, which picks the column of the cell.
+    fn get_x(&self) -> T;
+
+    /// Return the Y value, which picks the row of the cell.
+    fn get_y(&self) -> U;
+
+    /// Return the value that is added to the cell.
+    fn get_value(&self) -> f32;
+}
+
+impl HeatmapDatum<String, String> for (String, String, f32) {
+    fn get_x(&self) -> String {
+        self.0.clone()
+    }
+
+    fn get_y(&self) -> String {
+        self.1.clone()
+    }
+
+    fn get_value(&self) -> f32 {
+        self.2
+    }
+}
+
+impl HeatmapDatum<String, String> for (&str, &str, f32) {
+    fn get_x(&self) -> String {
+        String::from(self.0)
+    }
+
+    fn get_y(&self) -> String {
+        String::from(self.1)
+    }
+
+    fn get_value(&self) -> f32 {
+        self.2
+    }
+}
+
+impl HeatmapDatum<String, String> for (&str, &str, i32) {
+    fn get_x(&self) -> String {
+        String::from(self.0)
+    }
+
+    fn get_y(&self) -> String {
+        String::from(self.1)
+    }
+
+    fn get_value(&self) -> f32 {
+        self.2 as f32
+    }
+}
+
+impl HeatmapDatum<f32, f32> for (f32, f32, f32) {
+    fn get_x(&self) -> f32 {
+        self.0
+    }
+
+    fn get_y(&self) -> f32 {
+        self.1
+    }
+
+    fn get_value(&self) -> f32 {
+        self.2
+    }
+}
+
+/// A single point, which counts as one in its cell.
+impl HeatmapDatum<f32, f32> for (f32, f32) {
+    fn get_x(&self) -> f32 {
+        self.0
+    }
+
+    fn get_y(&self) -> f32 {
+        self.1
+    }
+
+    fn get_value(&self) -> f32 {
+        1_f32
+    }
+}
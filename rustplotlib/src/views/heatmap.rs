@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use svg::node::Node;
+use svg::node::element::Group;
+use crate::components::heatmap::HeatmapCell;
+use crate::colors::scale::ColorScale;
+use crate::Scale;
+use crate::views::datum::HeatmapDatum;
+use crate::views::View;
+use crate::components::DatumRepresentation;
+use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::ChartError;
+
+/// The number of colors shown in the color bar of the legend.
+const COLOR_BAR_STEPS: usize = 20;
+
+/// The horizontal and vertical range a cell covers, and the sum of its values.
+type CellTotal = ((f32, f32), (f32, f32), f32);
+
+/// A dimension a heatmap can be laid out along. Every category of a band scale
+/// gets its own cell, while values on a linear scale are binned into cells.
+pub trait HeatmapDimension: Display + Sized {
+    /// Return an identifier of the cell the value falls into, and where the cell
+    /// starts and ends on the scale's range.
+    fn get_cell(&self, scale: &dyn Scale<Self>, bin_size: f32) -> (String, f32, f32);
+}
+
+impl HeatmapDimension for String {
+    fn get_cell(&self, scale: &dyn Scale<Self>, _bin_size: f32) -> (String, f32, f32) {
+        let start = scale.scale(self);
+        (self.clone(), start, start + scale.bandwidth().unwrap_or(0_f32))
+    }
+}
+
+impl HeatmapDimension for f32 {
+    fn get_cell(&self, scale: &dyn Scale<Self>, bin_size: f32) -> (String, f32, f32) {
+        let bin = (self / bin_size).floor();
+        (bin.to_string(), scale.scale(&(bin * bin_size)), scale.scale(&((bin + 1_f32) * bin_size)))
+    }
+}
+
+/// A View that colors the cells of a grid by the sum of the values that fall into them.
+pub struct HeatmapView<T: HeatmapDimension, U: HeatmapDimension> {
+    entries: Vec<HeatmapCell>,
+    color_scale: ColorScale,
+    color_domain: Option<Vec<f32>>,
+    x_scale: Option<Box<dyn Scale<T>>>,
+    y_scale: Option<Box<dyn Scale<U>>>,
+    x_bin_size: f32,
+    y_bin_size: f32,
+    labels_visible: bool,
+    rounding_precision: Option<usize>,
+    custom_data_label: String,
+    legend_colors: Vec<String>,
+    legend_range: (String, String),
+}
+
+impl<T: HeatmapDimension, U: HeatmapDimension> Default for HeatmapView<T, U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: HeatmapDimension, U: HeatmapDimension> HeatmapView<T, U> {
+    /// Create a new empty instance of the view.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            color_scale: ColorScale::viridis(),
+            color_domain: None,
+            x_scale: None,
+            y_scale: None,
+            x_bin_size: 1_f32,
+            y_bin_size: 1_f32,
+            labels_visible: false,
+            rounding_precision: None,
+            custom_data_label: String::new(),
+            legend_colors: Vec::new(),
+            legend_range: (String::new(), String::new()),
+        }
+    }
+
+    /// Set the scale for the X dimension.
+    pub fn set_x_scale(mut self, scale: &(impl Scale<T> + Clone + 'static)) -> Self {
+        self.x_scale = Some(Box::new(scale.clone()));
+        self
+    }
+
+    /// Set the scale for the Y dimension.
+    pub fn set_y_scale(mut self, scale: &(impl Scale<U> + Clone + 'static)) -> Self {
+        self.y_scale = Some(Box::new(scale.clone()));
+        self
+    }
+
+    /// Set the color scale the cells are colored with. Defaults to [ColorScale::viridis].
+    pub fn set_color_scale(mut self, color_scale: ColorScale) -> Self {
+        self.color_scale = color_scale;
+        self
+    }
+
+    /// Set the values the ends of the color scale stand for. By default they are
+    /// the smallest and the biggest cell, and the domain of the color scale is ignored.
+    pub fn set_color_domain(mut self, domain: Vec<f32>) -> Self {
+        self.color_domain = Some(domain);
+        self
+    }
+
+    /// Set the size of the cells along linear scales, in the units of their domains.
+    /// Cells start at multiples of the size. Band scales ignore this and use their bands.
+    pub fn set_bin_size(mut self, x_bin_size: f32, y_bin_size: f32) -> Self {
+        self.x_bin_size = if x_bin_size > 0_f32 { x_bin_size } else { 1_f32 };
+        self.y_bin_size = if y_bin_size > 0_f32 { y_bin_size } else { 1_f32 };
+        self
+    }
+
+    /// Set whether the value of every cell is written on it.
+    pub fn set_label_visibility(mut self, label_visibility: bool) -> Self {
+        self.labels_visible = label_visibility;
+        self
+    }
+
+    /// Set the precision to which value labels should be rounded.
+    pub fn set_label_rounding_precision(mut self, nr_of_digits: usize) -> Self {
+        self.rounding_precision = Some(nr_of_digits);
+        self
+    }
+
+    /// Set the label shown next to the color bar in the legend.
+    pub fn set_custom_data_label(mut self, label: String) -> Self {
+        self.custom_data_label = label;
+        self
+    }
+
+    /// Load and process a dataset of HeatmapDatum points.
+    pub fn load_data(mut self, data: &[impl HeatmapDatum<T, U>]) -> Result<Self, ChartError> {
+        let x_scale = self.x_scale.as_deref().ok_or(ChartError::MissingScale("X"))?;
+        let y_scale = self.y_scale.as_deref().ok_or(ChartError::MissingScale("Y"))?;
+
+        // Add up the values that fall into the same cell.
+        let mut cells: HashMap<(String, String), CellTotal> = HashMap::new();
+        for datum in data.iter() {
+            let (x, y) = (datum.get_x(), datum.get_y());
+            if !x_scale.contains(&x) {
+                return Err(ChartError::NotInDomain(x.to_string()));
+            }
+            if !y_scale.contains(&y) {
+                return Err(ChartError::NotInDomain(y.to_string()));
+            }
+
+            let (column, x_start, x_end) = x.get_cell(x_scale, self.x_bin_size);
+            let (row, y_start, y_end) = y.get_cell(y_scale, self.y_bin_size);
+            cells.entry((column, row)).or_insert(((x_start, x_end), (y_start, y_end), 0_f32)).2 += datum.get_value();
+        }
+
+        if cells.is_empty() {
+            return Ok(self);
+        }
+
+        let domain = match self.color_domain {
+            Some(ref domain) => domain.clone(),
+            None => {
+                let values = cells.values().map(|(_, _, value)| *value);
+                vec![values.clone().fold(f32::INFINITY, f32::min), values.fold(f32::NEG_INFINITY, f32::max)]
+            },
+        };
+        let color_scale = self.color_scale.clone().set_domain(domain.clone());
+
+        for (x_range, y_range, value) in cells.into_values() {
+            let color = color_scale.scale(&value);
+            let label = if self.labels_visible {
+                let label_color = if color.is_dark() { "#fff" } else { "#333" };
+                Some((self.format_value(value), label_color.to_string()))
+            } else {
+                None
+            };
+            self.entries.push(HeatmapCell::new(x_range, y_range, color.as_hex(), label));
+        }
+
+        self.legend_colors = color_scale.sample(COLOR_BAR_STEPS).iter().map(|color| color.as_hex()).collect();
+        if let (Some(low), Some(high)) = (domain.first(), domain.last()) {
+            self.legend_range = (self.format_value(*low), self.format_value(*high));
+        }
+
+        Ok(self)
+    }
+
+    /// Format a value for a label, rounding it if a precision was set.
+    fn format_value(&self, value: f32) -> String {
+        match &self.rounding_precision {
+            None => value.to_string(),
+            Some(nr_of_digits) => format!("{:.1$}", value, nr_of_digits),
+        }
+    }
+}
+
+impl<T: HeatmapDimension, U: HeatmapDimension> View for HeatmapView<T, U> {
+    /// Generate the SVG representation of the view.
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new();
+
+        for entry in self.entries.iter() {
+            let child_svg = entry.to_svg()?;
+            group.append(child_svg);
+        }
+
+        Ok(group)
+    }
+
+    /// Return the legend entries that this view represents, which is a color bar
+    /// running from the lowest to the highest value of the color scale.
+    fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        if self.legend_colors.is_empty() {
+            return Vec::new();
+        }
+
+        let (low, high) = self.legend_range.clone();
+        vec![LegendEntry::new(LegendMarkerType::ColorBar(self.legend_colors.clone(), low, high), String::new(), String::from("none"), self.custom_data_label.clone())]
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+use svg::node::Node;
+use svg::node::element::Group;
+use crate::components::histogram::HistogramBin;
+use crate::colors::Color;
+use crate::{Scale, HistogramDatum};
+use crate::scales::ScaleType;
+use crate::components::DatumRepresentation;
+use crate::views::View;
+use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::stats::{freedman_diaconis_width, sorted, sturges_bin_count};
+use crate::ChartError;
+
+/// Set how the values of a histogram are split into bins.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Binning {
+    /// Split the range of the values into this many bins.
+    Count(usize),
+    /// Use bins of this width, starting at a multiple of it.
+    Width(f32),
+    /// Pick the number of bins with Sturges' rule, which suits small datasets.
+    Sturges,
+    /// Pick the width of the bins with the Freedman-Diaconis rule, which copes well with outliers.
+    FreedmanDiaconis,
+}
+
+/// Set how the histograms of different keys are combined.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HistogramMode {
+    /// The bins of every key start at zero and are drawn over each other.
+    Overlaid,
+    /// The bins of every key are stacked on top of each other.
+    Stacked,
+}
+
+/// The bins of a dataset, which all have the same width.
+struct Bins {
+    start: f32,
+    width: f32,
+    /// The number of values of each key in every bin, indexed by key first.
+    counts: Vec<Vec<f32>>,
+}
+
+impl Bins {
+    /// Return the highest point the histogram reaches.
+    fn get_max(&self, mode: HistogramMode) -> f32 {
+        let bin_count = self.counts.first().map(|counts| counts.len()).unwrap_or(0);
+        (0..bin_count)
+            .map(|bin| {
+                let counts = self.counts.iter().map(|counts| counts[bin]);
+                match mode {
+                    HistogramMode::Overlaid => counts.fold(0_f32, f32::max),
+                    HistogramMode::Stacked => counts.sum(),
+                }
+            })
+            .fold(0_f32, f32::max)
+    }
+}
+
+/// A View that counts how many values fall into each bin of their range and
+/// represents the counts as adjacent bars.
+pub struct HistogramView {
+    entries: Vec<HistogramBin>,
+    keys: Vec<String>,
+    colors: Vec<Color>,
+    color_map: HashMap<String, String>,
+    x_scale: Option<Box<dyn Scale<f32>>>,
+    y_scale: Option<Box<dyn Scale<f32>>>,
+    custom_data_label: String,
+    binning: Binning,
+    mode: HistogramMode,
+    opacity: Option<f32>,
+}
+
+impl Default for HistogramView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HistogramView {
+    /// Create a new empty instance of the view.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            keys: Vec::new(),
+            colors: Color::color_scheme_10(),
+            color_map: HashMap::new(),
+            x_scale: None,
+            y_scale: None,
+            custom_data_label: String::new(),
+            binning: Binning::FreedmanDiaconis,
+            mode: HistogramMode::Overlaid,
+            opacity: None,
+        }
+    }
+
+    /// Set the scale for the X dimension.
+    pub fn set_x_scale(mut self, scale: &(impl Scale<f32> + Clone + 'static)) -> Self {
+        self.x_scale = Some(Box::new(scale.clone()));
+        self
+    }
+
+    /// Set the scale for the Y dimension.
+    pub fn set_y_scale(mut self, scale: &(impl Scale<f32> + Clone + 'static)) -> Self {
+        self.y_scale = Some(Box::new(scale.clone()));
+        self
+    }
+
+    /// Set the keys in case of a histogram of several sets of values.
+    pub fn set_keys(mut self, keys: Vec<String>) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Set the color palette of the view.
+    pub fn set_colors(mut self, colors: Vec<Color>) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Set custom label for the dataset.
+    /// This will work when the dataset represents only a single
+    /// type of data (i.e. there are no different "keys" by which to
+    /// differentiate data), otherwise, this will have no effect.
+    pub fn set_custom_data_label(mut self, label: String) -> Self {
+        self.custom_data_label = label;
+        self
+    }
+
+    /// Set how the values are split into bins. Defaults to [Binning::FreedmanDiaconis].
+    pub fn set_binning(mut self, binning: Binning) -> Self {
+        self.binning = binning;
+        self
+    }
+
+    /// Set how the histograms of different keys are combined.
+    pub fn set_mode(mut self, mode: HistogramMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the opacity of the bins. By default overlaid histograms of several
+    /// keys are see-through, so that the ones at the back stay visible.
+    pub fn set_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = Some(opacity.clamp(0_f32, 1_f32));
+        self
+    }
+
+    /// Return the X and Y domains that fit the histogram of the dataset, to set on
+    /// the scales before loading the data. The Y domain goes from zero to the tallest bin.
+    pub fn get_domains(&self, data: &[impl HistogramDatum]) -> (Vec<f32>, Vec<f32>) {
+        let keys = if self.keys.is_empty() { Self::extract_keys(data) } else { self.keys.clone() };
+        match self.compute_bins(data, &keys) {
+            Some(bins) => {
+                let bin_count = bins.counts[0].len() as f32;
+                (vec![bins.start, bins.start + bins.width * bin_count], vec![0_f32, bins.get_max(self.mode)])
+            },
+            None => (vec![0_f32, 1_f32], vec![0_f32, 1_f32]),
+        }
+    }
+
+    /// Load and process a dataset of HistogramDatum points.
+    pub fn load_data(mut self, data: &[impl HistogramDatum]) -> Result<Self, ChartError> {
+        let x_scale = match self.x_scale.as_deref() {
+            Some(scale) if scale.get_type() == ScaleType::Linear => scale,
+            _ => return Err(ChartError::WrongScaleType { dimension: "X", expected: "Linear" }),
+        };
+        let y_scale = match self.y_scale.as_deref() {
+            Some(scale) if scale.get_type() == ScaleType::Linear => scale,
+            _ => return Err(ChartError::WrongScaleType { dimension: "Y", expected: "Linear" }),
+        };
+
+        if self.colors.is_empty() {
+            return Err(ChartError::NoColors);
+        }
+
+        // If no keys were explicitly provided, extract the keys from the data.
+        if self.keys.is_empty() {
+            self.keys = Self::extract_keys(data);
+        }
+
+        for (i, key) in self.keys.iter().enumerate() {
+            self.color_map.insert(key.clone(), self.colors[i % self.colors.len()].as_hex());
+        }
+
+        let bins = match self.compute_bins(data, &self.keys) {
+            Some(bins) => bins,
+            None => return Ok(self),
+        };
+
+        let opacity = match self.opacity {
+            Some(opacity) => opacity,
+            None if self.mode == HistogramMode::Overlaid && self.keys.len() > 1 => 0.6,
+            None => 1_f32,
+        };
+
+        let mut stacked = vec![0_f32; bins.counts[0].len()];
+        for (key, counts) in self.keys.iter().zip(bins.counts.iter()) {
+            for (bin, count) in counts.iter().enumerate() {
+                if *count == 0_f32 {
+                    continue;
+                }
+
+                let bin_start = bins.start + bin as f32 * bins.width;
+                let base = match self.mode {
+                    HistogramMode::Overlaid => 0_f32,
+                    HistogramMode::Stacked => stacked[bin],
+                };
+                stacked[bin] = base + count;
+
+                self.entries.push(HistogramBin::new(
+                    (x_scale.scale(&bin_start), x_scale.scale(&(bin_start + bins.width))),
+                    (y_scale.scale(&base), y_scale.scale(&(base + count))),
+                    self.color_map.get(key).unwrap().clone(),
+                    opacity,
+                ));
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Split the values of the given keys into bins and count them.
+    /// Return `None` when there are no values to count.
+    fn compute_bins(&self, data: &[impl HistogramDatum], keys: &[String]) -> Option<Bins> {
+        let values: Vec<f32> = data.iter()
+            .filter(|datum| keys.contains(&datum.get_key()))
+            .map(|datum| datum.get_value())
+            .collect();
+        let values = sorted(&values);
+        let (min, max) = (*values.first()?, *values.last()?);
+
+        let (start, width, bin_count) = match self.binning {
+            Binning::Width(width) if width > 0_f32 => {
+                let start = (min / width).floor() * width;
+                (start, width, ((max - start) / width).floor() as usize + 1)
+            },
+            Binning::FreedmanDiaconis if freedman_diaconis_width(&values).is_some() => {
+                let width = freedman_diaconis_width(&values).unwrap();
+                (min, width, ((max - min) / width).ceil().max(1_f32) as usize)
+            },
+            Binning::Count(count) => (min, (max - min) / count.max(1) as f32, count.max(1)),
+            _ => {
+                let count = sturges_bin_count(values.len());
+                (min, (max - min) / count as f32, count)
+            },
+        };
+
+        // When all the values are the same, they all go into a single bin around them.
+        let (start, width, bin_count) = if width > 0_f32 { (start, width, bin_count) } else { (min - 0.5, 1_f32, 1) };
+
+        let mut counts = vec![vec![0_f32; bin_count]; keys.len()];
+        for datum in data.iter() {
+            let value = datum.get_value();
+            if value.is_nan() {
+                continue;
+            }
+            if let Some(index) = keys.iter().position(|key| *key == datum.get_key()) {
+                // The biggest value closes the last bin rather than opening a new one.
+                let bin = (((value - start) / width).floor().max(0_f32) as usize).min(bin_count - 1);
+                counts[index][bin] += 1_f32;
+            }
+        }
+
+        Some(Bins { start, width, counts })
+    }
+
+    /// Extract the list of keys to use when overlaying or stacking and coloring the bins.
+    fn extract_keys(data: &[impl HistogramDatum]) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        for datum in data.iter() {
+            if !keys.contains(&datum.get_key()) {
+                keys.push(datum.get_key());
+            }
+        }
+        keys
+    }
+}
+
+impl View for HistogramView {
+    /// Generate the SVG representation of the view.
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new();
+
+        for entry in self.entries.iter() {
+            let child_svg = entry.to_svg()?;
+            group.append(child_svg);
+        }
+
+        Ok(group)
+    }
+
+    /// Return the legend entries that this view represents.
+    fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        let mut entries = Vec::new();
+
+        // If there is a single key and it is an empty string (meaning
+        // the dataset consists only of values), return the custom data label.
+        if self.keys.len() == 1 && self.keys[0].is_empty() {
+            entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(&self.keys[0]).unwrap().clone(), String::from("none"), self.custom_data_label.clone()));
+        } else {
+            for key in self.keys.iter() {
+                entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(key).unwrap().clone(), String::from("none"), key.clone()));
+            }
+        }
+
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bins(binning: Binning, data: &[impl HistogramDatum]) -> Option<Bins> {
+        let view = HistogramView::new().set_binning(binning);
+        let keys = HistogramView::extract_keys(data);
+        view.compute_bins(data, &keys)
+    }
+
+    #[test]
+    fn no_values_means_no_bins() {
+        let empty: Vec<f32> = Vec::new();
+        assert!(bins(Binning::Sturges, &empty).is_none());
+        assert!(bins(Binning::Sturges, &vec![f32::NAN]).is_none());
+        assert_eq!(HistogramView::new().get_domains(&empty), (vec![0_f32, 1_f32], vec![0_f32, 1_f32]));
+    }
+
+    #[test]
+    fn equal_values_go_into_one_bin_around_them() {
+        for binning in [Binning::Count(5), Binning::Sturges, Binning::FreedmanDiaconis] {
+            let bins = bins(binning, &vec![3_f32, 3_f32, 3_f32]).unwrap();
+            assert_eq!((bins.start, bins.width), (2.5, 1_f32));
+            assert_eq!(bins.counts, vec![vec![3_f32]]);
+        }
+        let bins = bins(Binning::Count(4), &vec![7_f32]).unwrap();
+        assert_eq!(bins.counts, vec![vec![1_f32]]);
+    }
+
+    #[test]
+    fn the_biggest_value_closes_the_last_bin() {
+        let bins = bins(Binning::Count(4), &vec![0_f32, 1_f32, 2_f32, 3_f32, 4_f32]).unwrap();
+        assert_eq!((bins.start, bins.width), (0_f32, 1_f32));
+        assert_eq!(bins.counts, vec![vec![1_f32, 1_f32, 1_f32, 2_f32]]);
+    }
+
+    #[test]
+    fn fixed_widths_start_at_a_multiple_of_the_width() {
+        let bins = bins(Binning::Width(5_f32), &vec![7_f32, 12_f32, 15_f32]).unwrap();
+        assert_eq!((bins.start, bins.width), (5_f32, 5_f32));
+        assert_eq!(bins.counts, vec![vec![1_f32, 1_f32, 1_f32]]);
+    }
+
+    #[test]
+    fn zero_counts_and_widths_fall_back() {
+        let values = vec![0_f32, 1_f32, 2_f32, 3_f32];
+        assert_eq!(bins(Binning::Count(0), &values).unwrap().counts[0].len(), 1);
+        let sturges = bins(Binning::Sturges, &values).unwrap();
+        let bad_width = bins(Binning::Width(0_f32), &values).unwrap();
+        assert_eq!(sturges.counts, bad_width.counts);
+    }
+
+    #[test]
+    fn keys_are_counted_separately() {
+        let data = vec![(1_f32, "a"), (1_f32, "b"), (2_f32, "b")];
+        let bins = bins(Binning::Count(2), &data).unwrap();
+        assert_eq!(bins.counts, vec![vec![1_f32, 0_f32], vec![1_f32, 1_f32]]);
+        assert_eq!(bins.get_max(HistogramMode::Overlaid), 1_f32);
+        assert_eq!(bins.get_max(HistogramMode::Stacked), 2_f32);
+    }
+
+    #[test]
+    fn domains_fit_the_bins() {
+        let view = HistogramView::new().set_binning(Binning::Count(2)).set_mode(HistogramMode::Stacked);
+        let data = vec![(0_f32, "a"), (0_f32, "b"), (4_f32, "a")];
+        assert_eq!(view.get_domains(&data), (vec![0_f32, 4_f32], vec![0_f32, 2_f32]));
+    }
+}
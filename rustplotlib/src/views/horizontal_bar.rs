@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use svg::node::Node;
 use svg::node::element::Group;
-use crate::components::bar::{Bar, BarBlock, BarLabelPosition};
+use crate::components::bar::{BarMode, Bar, BarBlock, BarLabelPosition};
 use crate::colors::Color;
 use crate::{Scale, BarDatum};
 use crate::scales::ScaleType;
@@ -9,9 +9,10 @@ use crate::components::DatumRepresentation;
 use crate::views::View;
 use crate::chart::Orientation;
 use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::ChartError;
 
 /// A View that represents data as horizontal bars.
-pub struct HorizontalBarView<'a> {
+pub struct HorizontalBarView {
     label_position: BarLabelPosition,
     labels_visible: bool,
     rounding_precision: Option<usize>,
@@ -19,12 +20,14 @@ pub struct HorizontalBarView<'a> {
     keys: Vec<String>,
     colors: Vec<Color>,
     color_map: HashMap<String, String>,
-    x_scale: Option<&'a dyn Scale<f32>>,
-    y_scale: Option<&'a dyn Scale<String>>,
+    x_scale: Option<Box<dyn Scale<f32>>>,
+    y_scale: Option<Box<dyn Scale<String>>>,
     custom_data_label: String,
+    mode: BarMode,
+    group_padding: f32,
 }
 
-impl<'a> HorizontalBarView<'a> {
+impl HorizontalBarView {
     /// Create a new empty instance of the view.
     pub fn new() -> Self {
         Self {
@@ -38,18 +41,20 @@ impl<'a> HorizontalBarView<'a> {
             x_scale: None,
             y_scale: None,
             custom_data_label: String::new(),
+            mode: BarMode::Stacked,
+            group_padding: 0.1,
         }
     }
 
     /// Set the scale for the X dimension.
-    pub fn set_x_scale(mut self, scale: &'a impl Scale<f32>) -> Self {
-        self.x_scale = Some(scale);
+    pub fn set_x_scale(mut self, scale: &(impl Scale<f32> + Clone + 'static)) -> Self {
+        self.x_scale = Some(Box::new(scale.clone()));
         self
     }
 
     /// Set the scale for the Y dimension.
-    pub fn set_y_scale(mut self, scale: &'a impl Scale<String>) -> Self {
-        self.y_scale = Some(scale);
+    pub fn set_y_scale(mut self, scale: &(impl Scale<String> + Clone + 'static)) -> Self {
+        self.y_scale = Some(Box::new(scale.clone()));
         self
     }
 
@@ -92,15 +97,32 @@ impl<'a> HorizontalBarView<'a> {
         self
     }
 
+    /// Set whether the bars of different keys are stacked or placed side by side.
+    pub fn set_bar_mode(mut self, mode: BarMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the fraction of each key's slot that is left empty between grouped bars.
+    /// This only has an effect in [BarMode::Grouped].
+    pub fn set_group_padding(mut self, padding: f32) -> Self {
+        self.group_padding = padding.max(0_f32).min(1_f32);
+        self
+    }
+
     /// Load and process a dataset of BarDatum points.
-    pub fn load_data(mut self, data: &Vec<impl BarDatum>) -> Result<Self, String> {
-        match self.x_scale {
-            Some(scale) if scale.get_type() == ScaleType::Linear => {},
-            _ => return Err("The X axis scale should be a Band scale.".to_string()),
-        }
-        match self.y_scale {
-            Some(scale) if scale.get_type() == ScaleType::Band => {},
-            _ => return Err("The Y axis scale should be a Linear scale.".to_string()),
+    pub fn load_data(mut self, data: &Vec<impl BarDatum>) -> Result<Self, ChartError> {
+        let x_scale = match self.x_scale.as_deref() {
+            Some(scale) if scale.get_type() == ScaleType::Linear => scale,
+            _ => return Err(ChartError::WrongScaleType { dimension: "X", expected: "Linear" }),
+        };
+        let y_scale = match self.y_scale.as_deref() {
+            Some(scale) if scale.get_type() == ScaleType::Band => scale,
+            _ => return Err(ChartError::WrongScaleType { dimension: "Y", expected: "Band" }),
+        };
+
+        if self.colors.is_empty() {
+            return Err(ChartError::NoColors);
         }
 
         // If no keys were explicitly provided, extract the keys from the data.
@@ -110,23 +132,26 @@ impl<'a> HorizontalBarView<'a> {
 
         // HashMap to group all data related to a category. This is needed when there
         // are many data entries under a single category as in a stacked bar chart.
-        let mut categories: HashMap<String, Vec<(&String, f32)>> = HashMap::new();
+        let mut categories: HashMap<String, Vec<(usize, &String, f32)>> = HashMap::new();
 
         // Organize entries based on the order of the keys first, since displayed data
         // should keep the order defined in the `keys` attribute.
-        for (i, key) in self.keys.iter_mut().enumerate() {
+        for (i, key) in self.keys.iter().enumerate() {
             // Map the key to the corresponding color.
             self.color_map.insert(key.clone(), self.colors[i % self.colors.len()].as_hex());
 
             for entry in data.iter() {
                 if entry.get_key() == *key {
                     let entry_category = entry.get_category();
+                    if !y_scale.contains(&entry_category) {
+                        return Err(ChartError::NotInDomain(entry_category));
+                    }
 
                     if !categories.contains_key(&entry_category) {
                         categories.insert(entry.get_category(), Vec::new());
                     }
                     if let Some(category_entries) = categories.get_mut(&entry_category) {
-                        category_entries.push((key, entry.get_value()));
+                        category_entries.push((i, key, entry.get_value()));
                     }
                 }
             }
@@ -134,28 +159,50 @@ impl<'a> HorizontalBarView<'a> {
 
         // Create a Bar entry for each category data that was grouped in the previous step.
         let mut bars = Vec::new();
-        let x_range_is_reversed = self.x_scale.unwrap().is_range_reversed();
 
         for (category, key_value_pairs) in categories.iter_mut() {
-            let mut value_acc = 0_f32;
-            let mut bar_blocks = Vec::new();
-            let mut stacked_start = self.x_scale.unwrap().scale(&value_acc);
-            let mut stacked_end = stacked_start;
-
-            for (key, value) in key_value_pairs.iter() {
-                value_acc += *value;
-
-                if x_range_is_reversed {
-                    stacked_end = stacked_start;
-                    stacked_start = self.x_scale.unwrap().scale(&value_acc);
-                } else {
-                    stacked_start = stacked_end;
-                    stacked_end = self.x_scale.unwrap().scale(&value_acc);
+            // In grouped mode every key gets its own slot within the band, so bars of the
+            // same key line up across categories even when some of them are missing.
+            if self.mode == BarMode::Grouped {
+                let slot = y_scale.bandwidth().unwrap() / self.keys.len() as f32;
+                let bar_width = slot * (1_f32 - self.group_padding);
+                let baseline = x_scale.scale(&0_f32);
+
+                for (index, key, value) in key_value_pairs.iter() {
+                    let end = x_scale.scale(value);
+                    let block = BarBlock::new(baseline.min(end), baseline.max(end), *value, self.color_map.get(*key).unwrap().clone());
+                    let offset = y_scale.scale(category) + *index as f32 * slot + (slot - bar_width) / 2_f32;
+                    bars.push(Bar::new(vec![block], Orientation::Horizontal, category.to_string(), self.label_position, self.labels_visible, self.rounding_precision, bar_width, offset));
                 }
-                bar_blocks.push(BarBlock::new(stacked_start, stacked_end, *value, self.color_map.get(*key).unwrap().clone()));
+                continue;
+            }
+
+            // In normalized mode every bar is scaled so that the sizes of its blocks add up to 1.
+            let total = match self.mode {
+                BarMode::Normalized => key_value_pairs.iter().map(|(_, _, value)| value.abs()).sum::<f32>(),
+                _ => 1_f32,
+            };
+            let total = if total > 0_f32 { total } else { 1_f32 };
+
+            // Positive values are stacked away from zero in one direction and negative
+            // values in the other, so that blocks with different signs never overlap.
+            let mut positive_acc = 0_f32;
+            let mut negative_acc = 0_f32;
+            let mut bar_blocks = Vec::new();
+
+            for (_, key, value) in key_value_pairs.iter() {
+                let value = *value / total;
+                let value_acc = if value < 0_f32 { &mut negative_acc } else { &mut positive_acc };
+                let stacked_start = x_scale.scale(value_acc);
+                *value_acc += value;
+                let stacked_end = x_scale.scale(value_acc);
+
+                // The block always goes from its smaller to its bigger coordinate, which accounts
+                // for scales that have the range in reversed order to match the SVG coordinate system.
+                bar_blocks.push(BarBlock::new(stacked_start.min(stacked_end), stacked_start.max(stacked_end), value, self.color_map.get(*key).unwrap().clone()));
             }
 
-            let bar = Bar::new(bar_blocks, Orientation::Horizontal, category.to_string(), self.label_position, self.labels_visible, self.rounding_precision, self.y_scale.unwrap().bandwidth().unwrap(), self.y_scale.unwrap().scale(category));
+            let bar = Bar::new(bar_blocks, Orientation::Horizontal, category.to_string(), self.label_position, self.labels_visible, self.rounding_precision, y_scale.bandwidth().unwrap(), y_scale.scale(category));
             bars.push(bar);
         }
 
@@ -187,9 +234,9 @@ impl<'a> HorizontalBarView<'a> {
     }
 }
 
-impl<'a> View<'a> for HorizontalBarView<'a> {
+impl View for HorizontalBarView {
     /// Generate the SVG representation of the view.
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
         let mut group = Group::new();
 
         for entry in self.entries.iter() {
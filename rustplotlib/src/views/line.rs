@@ -6,12 +6,13 @@ use crate::components::scatter::{ScatterPoint, MarkerType, PointLabelPosition};
 use crate::colors::Color;
 use crate::{Scale, LineSeries};
 use crate::views::datum::PointDatum;
-use crate::views::View;
+use crate::views::{View, check_points};
 use crate::components::DatumRepresentation;
 use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::ChartError;
 
 /// A View that represents data as a scatter plot.
-pub struct LineSeriesView<'a, T: Display, U: Display> {
+pub struct LineSeriesView<T: Display, U: Display> {
     labels_visible: bool,
     label_position: PointLabelPosition,
     marker_type: MarkerType,
@@ -19,12 +20,12 @@ pub struct LineSeriesView<'a, T: Display, U: Display> {
     colors: Vec<Color>,
     keys: Vec<String>,
     color_map: HashMap<String, String>,
-    x_scale: Option<&'a dyn Scale<T>>,
-    y_scale: Option<&'a dyn Scale<U>>,
+    x_scale: Option<Box<dyn Scale<T>>>,
+    y_scale: Option<Box<dyn Scale<U>>>,
     custom_data_label: String,
 }
 
-impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
+impl<T: Display, U: Display> LineSeriesView<T, U> {
     /// Create a new empty instance of the view.
     pub fn new() -> Self {
         Self {
@@ -42,14 +43,14 @@ impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
     }
 
     /// Set the scale for the X dimension.
-    pub fn set_x_scale(mut self, scale: &'a impl Scale<T>) -> Self {
-        self.x_scale = Some(scale);
+    pub fn set_x_scale(mut self, scale: &(impl Scale<T> + Clone + 'static)) -> Self {
+        self.x_scale = Some(Box::new(scale.clone()));
         self
     }
 
     /// Set the scale for the Y dimension.
-    pub fn set_y_scale(mut self, scale: &'a impl Scale<U>) -> Self {
-        self.y_scale = Some(scale);
+    pub fn set_y_scale(mut self, scale: &(impl Scale<U> + Clone + 'static)) -> Self {
+        self.y_scale = Some(Box::new(scale.clone()));
         self
     }
 
@@ -93,14 +94,12 @@ impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
     }
 
     /// Load and process a dataset of BarDatum points.
-    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, String> {
-        match self.x_scale {
-            Some(_) => {},
-            _ => return Err("Please provide a scale for the X dimension before loading data".to_string()),
-        }
-        match self.y_scale {
-            Some(_) => {},
-            _ => return Err("Please provide a scale for the Y dimension before loading data".to_string()),
+    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, ChartError> {
+        let x_scale = self.x_scale.as_deref().ok_or(ChartError::MissingScale("X"))?;
+        let y_scale = self.y_scale.as_deref().ok_or(ChartError::MissingScale("Y"))?;
+
+        if self.colors.is_empty() {
+            return Err(ChartError::NoColors);
         }
 
         // If no keys were explicitly provided, extract the keys from the data.
@@ -115,23 +114,25 @@ impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
             self.color_map.insert(key.clone(), self.colors[i % self.colors.len()].as_hex());
         }
 
+        check_points(data, x_scale, y_scale)?;
+
         for key in self.keys.iter() {
 
             let points = data.iter().filter(|datum| &datum.get_key() == key).map(|datum| {
-                let scaled_x = self.x_scale.unwrap().scale(&datum.get_x());
-                let scaled_y = self.y_scale.unwrap().scale(&datum.get_y());
+                let scaled_x = x_scale.scale(&datum.get_x());
+                let scaled_y = y_scale.scale(&datum.get_y());
                 let y_bandwidth_offset = {
-                    if self.y_scale.unwrap().is_range_reversed() {
-                        -self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
+                    if y_scale.is_range_reversed() {
+                        -y_scale.bandwidth().unwrap() / 2_f32
                     } else {
-                        self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
+                        y_scale.bandwidth().unwrap() / 2_f32
                     }
                 };
                 let x_bandwidth_offset = {
-                    if self.x_scale.unwrap().is_range_reversed() {
-                        -self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
+                    if x_scale.is_range_reversed() {
+                        -x_scale.bandwidth().unwrap() / 2_f32
                     } else {
-                        self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
+                        x_scale.bandwidth().unwrap() / 2_f32
                     }
                 };
                 ScatterPoint::new(scaled_x + x_bandwidth_offset, scaled_y + y_bandwidth_offset, self.marker_type, 5, datum.get_x(), datum.get_y(), self.label_position, self.labels_visible, true,self.color_map.get(&datum.get_key()).unwrap().clone())
@@ -160,9 +161,9 @@ impl<'a, T: Display, U: Display> LineSeriesView<'a, T, U> {
 
 }
 
-impl<'a, T: Display, U: Display> View<'a> for LineSeriesView<'a, T, U> {
+impl<T: Display + Send + Sync, U: Display + Send + Sync> View for LineSeriesView<T, U> {
     /// Generate the SVG representation of the view.
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
         let mut group = Group::new();
 
         for entry in self.entries.iter() {
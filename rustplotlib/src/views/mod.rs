@@ -1,5 +1,9 @@
 use svg::node::element::Group;
+use std::collections::HashMap;
+use std::fmt::Display;
 use crate::components::legend::LegendEntry;
+use crate::views::datum::{PointDatum, SampleDatum};
+use crate::{ChartError, Scale};
 
 pub mod vertical_bar;
 pub mod horizontal_bar;
@@ -7,10 +11,63 @@ pub mod scatter;
 pub mod datum;
 pub mod line;
 pub mod area;
+pub mod box_plot;
+pub mod violin;
+pub mod histogram;
+pub mod heatmap;
+pub mod pie;
 
 /// A trait that defines a View of a dataset that can be rendered within a chart.
-pub trait View<'a> {
-    fn to_svg(&self) -> Result<Group, String>;
+/// Views own everything they need, so a chart holding them can be sent between threads.
+pub trait View: Send + Sync {
+    fn to_svg(&self) -> Result<Group, ChartError>;
+
+    /// Generate the SVG of the view within an area of the given size. Views that are
+    /// placed by their scales don't need the size, so by default this is [View::to_svg].
+    fn to_svg_in_area(&self, _width: f32, _height: f32) -> Result<Group, ChartError> {
+        self.to_svg()
+    }
 
     fn get_legend_entries(&self) -> Vec<LegendEntry>;
 }
+
+/// Check that every point can be placed by the scales before any of them get scaled.
+pub(crate) fn check_points<T: Display, U: Display>(data: &[impl PointDatum<T, U>], x_scale: &dyn Scale<T>, y_scale: &dyn Scale<U>) -> Result<(), ChartError> {
+    for datum in data.iter() {
+        if !x_scale.contains(&datum.get_x()) {
+            return Err(ChartError::NotInDomain(datum.get_x().to_string()));
+        }
+        if !y_scale.contains(&datum.get_y()) {
+            return Err(ChartError::NotInDomain(datum.get_y().to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Extract the keys of a distribution dataset, in the order they first appear.
+pub(crate) fn extract_sample_keys(data: &[impl SampleDatum]) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for datum in data.iter() {
+        if !keys.contains(&datum.get_key()) {
+            keys.push(datum.get_key());
+        }
+    }
+    keys
+}
+
+/// Collect the samples of every distribution, which is every pair of a category and one of the keys.
+/// Return the category, the index of the key and the samples of each distribution.
+pub(crate) fn group_samples(data: &[impl SampleDatum], keys: &[String], x_scale: &dyn Scale<String>) -> Result<Vec<(String, usize, Vec<f32>)>, ChartError> {
+    let mut distributions: HashMap<(String, usize), Vec<f32>> = HashMap::new();
+    for datum in data.iter() {
+        let category = datum.get_category();
+        if !x_scale.contains(&category) {
+            return Err(ChartError::NotInDomain(category));
+        }
+        if let Some(index) = keys.iter().position(|key| *key == datum.get_key()) {
+            distributions.entry((category, index)).or_default().push(datum.get_value());
+        }
+    }
+
+    Ok(distributions.into_iter().map(|((category, index), samples)| (category, index, samples)).collect())
+}
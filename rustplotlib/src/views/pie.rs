@@ -0,0 +1,290 @@
+use svg::node::Node;
+use svg::node::Text as TextNode;
+use svg::node::element::{Group, Text};
+use crate::components::pie::{Slice, SliceLabelPosition, LEADER_LINE_LENGTH, LEADER_LINE_TAIL};
+use crate::colors::Color;
+use crate::BarDatum;
+use crate::components::DatumRepresentation;
+use crate::views::View;
+use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::ChartError;
+
+/// The radius of a pie that is drawn without an area to fit into.
+const DEFAULT_RADIUS: f32 = 100_f32;
+/// The width of a character in the 12px labels, with some buffer.
+const AVG_LETTER_WIDTH: f32 = 7_f32;
+
+/// A View that represents the share of every category in the total as a slice of a pie.
+/// It doesn't use scales or axes, and fits itself into the inner area of the chart.
+pub struct PieView {
+    slices: Vec<(String, f32, String)>,
+    keys: Vec<String>,
+    colors: Vec<Color>,
+    labels_visible: bool,
+    percentages_visible: bool,
+    label_position: SliceLabelPosition,
+    radius: Option<f32>,
+    hole_size: f32,
+    center_label: String,
+}
+
+impl Default for PieView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PieView {
+    /// Create a new empty instance of the view.
+    pub fn new() -> Self {
+        Self {
+            slices: Vec::new(),
+            keys: Vec::new(),
+            colors: Color::color_scheme_10(),
+            labels_visible: true,
+            percentages_visible: true,
+            label_position: SliceLabelPosition::Outside,
+            radius: None,
+            hole_size: 0_f32,
+            center_label: String::new(),
+        }
+    }
+
+    /// Set the categories to show and the order of their slices, clockwise from the top.
+    pub fn set_keys(mut self, keys: Vec<String>) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Set the color palette of the view.
+    pub fn set_colors(mut self, colors: Vec<Color>) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Set whether the slices are labeled with their categories.
+    pub fn set_label_visibility(mut self, label_visibility: bool) -> Self {
+        self.labels_visible = label_visibility;
+        self
+    }
+
+    /// Set whether the slices are labeled with their share of the total.
+    pub fn set_percentage_visibility(mut self, percentage_visibility: bool) -> Self {
+        self.percentages_visible = percentage_visibility;
+        self
+    }
+
+    /// Set the positioning of the labels.
+    pub fn set_label_position(mut self, label_position: SliceLabelPosition) -> Self {
+        self.label_position = label_position;
+        self
+    }
+
+    /// Set the radius of the pie. By default the pie is as big as the area allows
+    /// while leaving room for the labels.
+    pub fn set_radius(mut self, radius: f32) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// Load and process a dataset of BarDatum points. The category of every datum is a
+    /// slice and values in the same category are added up. Keys are ignored, and slices
+    /// that don't add up to more than zero are left out since they can't be drawn.
+    pub fn load_data(mut self, data: &[impl BarDatum]) -> Result<Self, ChartError> {
+        if self.colors.is_empty() {
+            return Err(ChartError::NoColors);
+        }
+
+        // If no keys were explicitly provided, take the categories in the order they appear.
+        if self.keys.is_empty() {
+            for datum in data.iter() {
+                if !self.keys.contains(&datum.get_category()) {
+                    self.keys.push(datum.get_category());
+                }
+            }
+        }
+
+        for (i, key) in self.keys.iter().enumerate() {
+            let value = data.iter()
+                .filter(|datum| datum.get_category() == *key)
+                .map(|datum| datum.get_value())
+                .sum::<f32>();
+            if value > 0_f32 {
+                self.slices.push((key.clone(), value, self.colors[i % self.colors.len()].as_hex()));
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Return the label of a slice, depending on which parts of it are visible.
+    fn slice_label(&self, category: &str, share: f32) -> Option<String> {
+        let percentage = format!("{:.0}%", share * 100_f32);
+        match (self.labels_visible, self.percentages_visible) {
+            (true, true) => Some(format!("{} ({})", category, percentage)),
+            (true, false) => Some(category.to_string()),
+            (false, true) => Some(percentage),
+            (false, false) => None,
+        }
+    }
+
+    /// Return how much room the labels need around the pie, horizontally and vertically.
+    fn label_room(&self) -> (f32, f32) {
+        if self.label_position == SliceLabelPosition::Inside {
+            return (0_f32, 0_f32);
+        }
+        let longest_label = self.slices.iter()
+            .filter_map(|(category, _, _)| self.slice_label(category, 1_f32))
+            .map(|label| label.len())
+            .max();
+        match longest_label {
+            Some(length) => (LEADER_LINE_LENGTH + LEADER_LINE_TAIL + 4_f32 + AVG_LETTER_WIDTH * length as f32, LEADER_LINE_LENGTH + 8_f32),
+            None => (0_f32, 0_f32),
+        }
+    }
+}
+
+impl View for PieView {
+    /// Generate the SVG representation of the view, in a square that fits its radius and labels.
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let (room_x, room_y) = self.label_room();
+        let radius = self.radius.unwrap_or(DEFAULT_RADIUS);
+        self.to_svg_in_area(2_f32 * (radius + room_x), 2_f32 * (radius + room_y))
+    }
+
+    /// Generate the SVG representation of the view, centered in the area.
+    fn to_svg_in_area(&self, width: f32, height: f32) -> Result<Group, ChartError> {
+        let mut group = Group::new()
+            .set("transform", format!("translate({},{})", width / 2_f32, height / 2_f32))
+            .set("class", "pie");
+
+        let (room_x, room_y) = self.label_room();
+        let outer_radius = self.radius
+            .unwrap_or_else(|| (width / 2_f32 - room_x).min(height / 2_f32 - room_y))
+            .max(10_f32);
+        let inner_radius = outer_radius * self.hole_size;
+
+        let total = self.slices.iter().map(|(_, value, _)| value).sum::<f32>();
+        let mut start_angle = 0_f32;
+        for (category, value, color) in self.slices.iter() {
+            let share = value / total;
+            let end_angle = start_angle + share * 2_f32 * std::f32::consts::PI;
+            let slice = Slice::new(
+                (start_angle, end_angle),
+                (inner_radius, outer_radius),
+                color.clone(),
+                self.slice_label(category, share),
+                self.label_position,
+            );
+            group.append(slice.to_svg()?);
+            start_angle = end_angle;
+        }
+
+        if !self.center_label.is_empty() && inner_radius > 0_f32 {
+            group.append(Text::new()
+                .set("x", 0)
+                .set("y", 0)
+                .set("dy", ".35em")
+                .set("text-anchor", "middle")
+                .set("font-family", "sans-serif")
+                .set("fill", "#555")
+                .set("font-size", "16px")
+                .add(TextNode::new(&self.center_label)));
+        }
+
+        Ok(group)
+    }
+
+    /// Return the legend entries that this view represents, one for every slice.
+    fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        self.slices.iter()
+            .map(|(category, _, color)| LegendEntry::new(LegendMarkerType::Square, color.clone(), String::from("none"), category.clone()))
+            .collect()
+    }
+}
+
+/// A View that represents the share of every category in the total as a slice of
+/// a ring, which leaves room in the middle for a label such as the total.
+pub struct DonutView {
+    pie: PieView,
+}
+
+impl Default for DonutView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DonutView {
+    /// Create a new empty instance of the view.
+    pub fn new() -> Self {
+        let mut pie = PieView::new();
+        pie.hole_size = 0.6;
+        Self { pie }
+    }
+
+    /// Set the categories to show and the order of their slices, clockwise from the top.
+    pub fn set_keys(self, keys: Vec<String>) -> Self {
+        Self { pie: self.pie.set_keys(keys) }
+    }
+
+    /// Set the color palette of the view.
+    pub fn set_colors(self, colors: Vec<Color>) -> Self {
+        Self { pie: self.pie.set_colors(colors) }
+    }
+
+    /// Set whether the slices are labeled with their categories.
+    pub fn set_label_visibility(self, label_visibility: bool) -> Self {
+        Self { pie: self.pie.set_label_visibility(label_visibility) }
+    }
+
+    /// Set whether the slices are labeled with their share of the total.
+    pub fn set_percentage_visibility(self, percentage_visibility: bool) -> Self {
+        Self { pie: self.pie.set_percentage_visibility(percentage_visibility) }
+    }
+
+    /// Set the positioning of the labels.
+    pub fn set_label_position(self, label_position: SliceLabelPosition) -> Self {
+        Self { pie: self.pie.set_label_position(label_position) }
+    }
+
+    /// Set the outer radius of the ring. By default the ring is as big as the area allows
+    /// while leaving room for the labels.
+    pub fn set_radius(self, radius: f32) -> Self {
+        Self { pie: self.pie.set_radius(radius) }
+    }
+
+    /// Set the size of the hole as a fraction of the outer radius. Defaults to 0.6.
+    pub fn set_hole_size(mut self, hole_size: f32) -> Self {
+        self.pie.hole_size = hole_size.clamp(0_f32, 0.95);
+        self
+    }
+
+    /// Set the text written in the hole of the ring.
+    pub fn set_center_label(mut self, label: String) -> Self {
+        self.pie.center_label = label;
+        self
+    }
+
+    /// Load and process a dataset of BarDatum points, the same way [PieView::load_data] does.
+    pub fn load_data(self, data: &[impl BarDatum]) -> Result<Self, ChartError> {
+        Ok(Self { pie: self.pie.load_data(data)? })
+    }
+}
+
+impl View for DonutView {
+    /// Generate the SVG representation of the view, in a square that fits its radius and labels.
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        self.pie.to_svg()
+    }
+
+    /// Generate the SVG representation of the view, centered in the area.
+    fn to_svg_in_area(&self, width: f32, height: f32) -> Result<Group, ChartError> {
+        self.pie.to_svg_in_area(width, height)
+    }
+
+    /// Return the legend entries that this view represents, one for every slice.
+    fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        self.pie.get_legend_entries()
+    }
+}
@@ -6,31 +6,34 @@ use crate::components::scatter::{ScatterPoint, MarkerType, PointLabelPosition};
 use crate::colors::Color;
 use crate::Scale;
 use crate::views::datum::PointDatum;
-use crate::views::View;
+use crate::views::{View, check_points};
 use crate::components::DatumRepresentation;
 use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::ChartError;
 
 /// A View that represents data as a scatter plot.
-pub struct ScatterView<'a, T: Display, U: Display> {
+pub struct ScatterView<T: Display, U: Display> {
     labels_visible: bool,
     label_position: PointLabelPosition,
     marker_type: MarkerType,
+    marker_size: usize,
     entries: Vec<ScatterPoint<T, U>>,
     colors: Vec<Color>,
     keys: Vec<String>,
     color_map: HashMap<String, String>,
-    x_scale: Option<&'a dyn Scale<T>>,
-    y_scale: Option<&'a dyn Scale<U>>,
+    x_scale: Option<Box<dyn Scale<T>>>,
+    y_scale: Option<Box<dyn Scale<U>>>,
     custom_data_label: String,
 }
 
-impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
+impl<T: Display, U: Display> ScatterView<T, U> {
     /// Create a new empty instance of the view.
     pub fn new() -> Self {
         Self {
             labels_visible: true,
             label_position: PointLabelPosition::NW,
             marker_type: MarkerType::Circle,
+            marker_size: 5,
             entries: Vec::new(),
             keys: Vec::new(),
             colors: Color::color_scheme_10(),
@@ -42,14 +45,14 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
     }
 
     /// Set the scale for the X dimension.
-    pub fn set_x_scale(mut self, scale: &'a impl Scale<T>) -> Self {
-        self.x_scale = Some(scale);
+    pub fn set_x_scale(mut self, scale: &(impl Scale<T> + Clone + 'static)) -> Self {
+        self.x_scale = Some(Box::new(scale.clone()));
         self
     }
 
     /// Set the scale for the Y dimension.
-    pub fn set_y_scale(mut self, scale: &'a impl Scale<U>) -> Self {
-        self.y_scale = Some(scale);
+    pub fn set_y_scale(mut self, scale: &(impl Scale<U> + Clone + 'static)) -> Self {
+        self.y_scale = Some(Box::new(scale.clone()));
         self
     }
 
@@ -71,6 +74,12 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
         self
     }
 
+    /// Set the size of the markers, which is the radius for circles and half the side for squares.
+    pub fn set_marker_size(mut self, marker_size: usize) -> Self {
+        self.marker_size = marker_size;
+        self
+    }
+
     /// Set the color palette of the view.
     pub fn set_colors(mut self, colors: Vec<Color>) -> Self {
         self.colors = colors;
@@ -93,14 +102,12 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
     }
 
     /// Load and process a dataset of BarDatum points.
-    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, String> {
-        match self.x_scale {
-            Some(_) => {},
-            _ => return Err("Please provide a scale for the X dimension before loading data".to_string()),
-        }
-        match self.y_scale {
-            Some(_) => {},
-            _ => return Err("Please provide a scale for the Y dimension before loading data".to_string()),
+    pub fn load_data(mut self, data: &Vec<impl PointDatum<T, U>>) -> Result<Self, ChartError> {
+        let x_scale = self.x_scale.as_deref().ok_or(ChartError::MissingScale("X"))?;
+        let y_scale = self.y_scale.as_deref().ok_or(ChartError::MissingScale("Y"))?;
+
+        if self.colors.is_empty() {
+            return Err(ChartError::NoColors);
         }
 
         // If no keys were explicitly provided, extract the keys from the data.
@@ -115,24 +122,29 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
             self.color_map.insert(key.clone(), self.colors[i % self.colors.len()].as_hex());
         }
 
+        check_points(data, x_scale, y_scale)?;
+        if let Some(datum) = data.iter().find(|datum| !self.color_map.contains_key(&datum.get_key())) {
+            return Err(ChartError::UnknownKey(datum.get_key()));
+        }
+
         for datum in data.iter() {
-            let scaled_x = self.x_scale.unwrap().scale(&datum.get_x());
-            let scaled_y = self.y_scale.unwrap().scale(&datum.get_y());
+            let scaled_x = x_scale.scale(&datum.get_x());
+            let scaled_y = y_scale.scale(&datum.get_y());
             let y_bandwidth_offset = {
-                if self.y_scale.unwrap().is_range_reversed() {
-                    -self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
+                if y_scale.is_range_reversed() {
+                    -y_scale.bandwidth().unwrap() / 2_f32
                 } else {
-                    self.y_scale.unwrap().bandwidth().unwrap() / 2_f32
+                    y_scale.bandwidth().unwrap() / 2_f32
                 }
             };
             let x_bandwidth_offset = {
-                if self.x_scale.unwrap().is_range_reversed() {
-                    -self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
+                if x_scale.is_range_reversed() {
+                    -x_scale.bandwidth().unwrap() / 2_f32
                 } else {
-                    self.x_scale.unwrap().bandwidth().unwrap() / 2_f32
+                    x_scale.bandwidth().unwrap() / 2_f32
                 }
             };
-            self.entries.push(ScatterPoint::new(scaled_x + x_bandwidth_offset, scaled_y + y_bandwidth_offset, self.marker_type, 5, datum.get_x(), datum.get_y(), self.label_position, self.labels_visible, true, self.color_map.get(&datum.get_key()).unwrap().clone()));
+            self.entries.push(ScatterPoint::new(scaled_x + x_bandwidth_offset, scaled_y + y_bandwidth_offset, self.marker_type, self.marker_size, datum.get_x(), datum.get_y(), self.label_position, self.labels_visible, true, self.color_map.get(&datum.get_key()).unwrap().clone()));
         }
 
         Ok(self)
@@ -155,9 +167,9 @@ impl<'a, T: Display, U: Display> ScatterView<'a, T, U> {
 
 }
 
-impl<'a, T: Display, U: Display> View<'a> for ScatterView<'a, T, U> {
+impl<T: Display + Send + Sync, U: Display + Send + Sync> View for ScatterView<T, U> {
     /// Generate the SVG representation of the view.
-    fn to_svg(&self) -> Result<Group, String> {
+    fn to_svg(&self) -> Result<Group, ChartError> {
         let mut group = Group::new();
 
         for entry in self.entries.iter() {
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use svg::node::Node;
+use svg::node::element::Group;
+use crate::components::violin::Violin;
+use crate::colors::Color;
+use crate::{Scale, SampleDatum};
+use crate::scales::ScaleType;
+use crate::components::DatumRepresentation;
+use crate::views::{View, extract_sample_keys, group_samples};
+use crate::components::legend::{LegendEntry, LegendMarkerType};
+use crate::stats::{kernel_density, Summary};
+use crate::ChartError;
+
+/// A View that shows the distribution of the samples in each category as a violin,
+/// which is the kernel density estimate of the samples mirrored around its center.
+pub struct ViolinView {
+    entries: Vec<Violin>,
+    keys: Vec<String>,
+    colors: Vec<Color>,
+    color_map: HashMap<String, String>,
+    x_scale: Option<Box<dyn Scale<String>>>,
+    y_scale: Option<Box<dyn Scale<f32>>>,
+    custom_data_label: String,
+    padding: f32,
+    bandwidth: Option<f32>,
+    resolution: usize,
+}
+
+impl Default for ViolinView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViolinView {
+    /// Create a new empty instance of the view.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            keys: Vec::new(),
+            colors: Color::color_scheme_10(),
+            color_map: HashMap::new(),
+            x_scale: None,
+            y_scale: None,
+            custom_data_label: String::new(),
+            padding: 0.1,
+            bandwidth: None,
+            resolution: 50,
+        }
+    }
+
+    /// Set the scale for the X dimension.
+    pub fn set_x_scale(mut self, scale: &(impl Scale<String> + Clone + 'static)) -> Self {
+        self.x_scale = Some(Box::new(scale.clone()));
+        self
+    }
+
+    /// Set the scale for the Y dimension.
+    pub fn set_y_scale(mut self, scale: &(impl Scale<f32> + Clone + 'static)) -> Self {
+        self.y_scale = Some(Box::new(scale.clone()));
+        self
+    }
+
+    /// Set the keys of the distributions shown side by side within each category.
+    pub fn set_keys(mut self, keys: Vec<String>) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Set the color palette of the view.
+    pub fn set_colors(mut self, colors: Vec<Color>) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Set custom label for the dataset.
+    /// This will work when the dataset represents only a single
+    /// type of data (i.e. there are no different "keys" by which to
+    /// differentiate data), otherwise, this will have no effect.
+    pub fn set_custom_data_label(mut self, label: String) -> Self {
+        self.custom_data_label = label;
+        self
+    }
+
+    /// Set the fraction of each distribution's slot that is left empty around its violin.
+    pub fn set_padding(mut self, padding: f32) -> Self {
+        self.padding = padding.clamp(0_f32, 1_f32);
+        self
+    }
+
+    /// Set the bandwidth of the kernel used to estimate the densities, in the units of the
+    /// samples. By default it is picked for each distribution with Silverman's rule of thumb.
+    pub fn set_bandwidth(mut self, bandwidth: f32) -> Self {
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+
+    /// Set at how many points the density of each distribution is estimated.
+    pub fn set_resolution(mut self, resolution: usize) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Load and process a dataset of SampleDatum points.
+    pub fn load_data(mut self, data: &[impl SampleDatum]) -> Result<Self, ChartError> {
+        let x_scale = match self.x_scale.as_deref() {
+            Some(scale) if scale.get_type() == ScaleType::Band => scale,
+            _ => return Err(ChartError::WrongScaleType { dimension: "X", expected: "Band" }),
+        };
+        let y_scale = match self.y_scale.as_deref() {
+            Some(scale) if scale.get_type() == ScaleType::Linear => scale,
+            _ => return Err(ChartError::WrongScaleType { dimension: "Y", expected: "Linear" }),
+        };
+
+        if self.colors.is_empty() {
+            return Err(ChartError::NoColors);
+        }
+
+        // If no keys were explicitly provided, extract the keys from the data.
+        if self.keys.is_empty() {
+            self.keys = extract_sample_keys(data);
+        }
+
+        for (i, key) in self.keys.iter().enumerate() {
+            self.color_map.insert(key.clone(), self.colors[i % self.colors.len()].as_hex());
+        }
+
+        // Every key gets its own slot within the band of a category.
+        let slot = x_scale.bandwidth().unwrap() / self.keys.len().max(1) as f32;
+        let violin_width = slot * (1_f32 - self.padding);
+
+        for (category, index, samples) in group_samples(data, &self.keys, x_scale)? {
+            let summary = match Summary::new(&samples, 1.5) {
+                Some(summary) => summary,
+                None => continue,
+            };
+
+            // Every violin is as wide as its slot allows at its densest point.
+            let density = kernel_density(&samples, self.bandwidth, self.resolution);
+            let max_density = density.iter().map(|(_, density)| *density).fold(0_f32, f32::max);
+            let outline = density.iter()
+                .map(|(value, density)| {
+                    let half_width = if max_density > 0_f32 { density / max_density * violin_width / 2_f32 } else { 0_f32 };
+                    (y_scale.scale(value), half_width)
+                })
+                .collect();
+
+            let offset = x_scale.scale(&category) + index as f32 * slot + (slot - violin_width) / 2_f32;
+            let violin = Violin::new(
+                outline,
+                (y_scale.scale(&summary.q1), y_scale.scale(&summary.median), y_scale.scale(&summary.q3)),
+                self.color_map.get(&self.keys[index]).unwrap().clone(),
+                violin_width,
+                offset,
+            );
+            self.entries.push(violin);
+        }
+
+        Ok(self)
+    }
+}
+
+impl View for ViolinView {
+    /// Generate the SVG representation of the view.
+    fn to_svg(&self) -> Result<Group, ChartError> {
+        let mut group = Group::new();
+
+        for entry in self.entries.iter() {
+            let child_svg = entry.to_svg()?;
+            group.append(child_svg);
+        }
+
+        Ok(group)
+    }
+
+    /// Return the legend entries that this view represents.
+    fn get_legend_entries(&self) -> Vec<LegendEntry> {
+        let mut entries = Vec::new();
+
+        // If there is a single key and it is an empty string (meaning
+        // the dataset consists only of categories and values), return
+        // the custom data label.
+        if self.keys.len() == 1 && self.keys[0].is_empty() {
+            entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(&self.keys[0]).unwrap().clone(), String::from("none"), self.custom_data_label.clone()));
+        } else {
+            for key in self.keys.iter() {
+                entries.push(LegendEntry::new(LegendMarkerType::Square, self.color_map.get(key).unwrap().clone(), String::from("none"), key.clone()));
+            }
+        }
+
+        entries
+    }
+}
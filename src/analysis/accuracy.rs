@@ -1,38 +1,144 @@
-use std::{ops::AddAssign, collections::BTreeMap};
+use std::{ops::AddAssign, collections::BTreeMap, cmp::Ordering};
 use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
-use crate::{models::RobotMatchInfo, DbPool, HttpResult, DatabaseError};
+use askama::Template;
+use crate::{models::RobotMatchInfo, DbPool, HttpResult, DatabaseError, schema::LeftTarmac};
+use super::options::{ChartQuery, ChartLayout, bad_query};
+use super::{MatchAverage, by_team, show_count};
 use diesel::prelude::*;
 
-// only for teleop currently
-#[derive(Default)]
-struct AccuracyInfo {
-    points: i32,
-    missed_points: i32,
+/// Shots made and missed at one goal in one phase
+#[derive(Clone, Copy, Default)]
+pub(super) struct GoalAccuracy {
+    made: f32,
+    missed: f32,
+}
+
+impl GoalAccuracy {
+    fn new(made: i32, missed: i32) -> Self {
+        GoalAccuracy { made: made as f32, missed: missed as f32 }
+    }
+
+    fn attempts(&self) -> f32 {
+        self.made + self.missed
+    }
+
+    /// the fraction of shots made, or None if nothing was shot
+    pub(super) fn accuracy(&self) -> Option<f32> {
+        if self.attempts() <= 0f32 {
+            None
+        } else {
+            Some(self.made / self.attempts())
+        }
+    }
+
+    /// the accuracy as a percentage for a table cell, with the number of shots behind it
+    pub(super) fn show(&self) -> String {
+        match self.accuracy() {
+            Some(a) => format!("{:.0}% of {}", a * 100f32, show_count(self.attempts())),
+            None => "–".to_string(),
+        }
+    }
+
+    fn averaged(self, entries: f32) -> Self {
+        GoalAccuracy { made: self.made / entries, missed: self.missed / entries }
+    }
+}
+
+impl AddAssign for GoalAccuracy {
+    fn add_assign(&mut self, rhs: Self) {
+        self.made += rhs.made;
+        self.missed += rhs.missed;
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct AccuracyInfo {
+    pub(super) matches: u32,
+    pub(super) auto_high: GoalAccuracy,
+    pub(super) auto_low: GoalAccuracy,
+    pub(super) teleop_high: GoalAccuracy,
+    pub(super) teleop_low: GoalAccuracy,
+    left_tarmac: f32,
 }
 
 impl AccuracyInfo {
-    fn accuracy(&self) -> f32 {
-        (self.points as f32) / ((self.points + self.missed_points) as f32)
+    /// teleop accuracy weighted by what each shot is worth, for the original accuracy chart
+    fn teleop_point_accuracy(&self) -> f32 {
+        let points = self.teleop_high.made * 2f32 + self.teleop_low.made;
+        let missed_points = self.teleop_high.missed * 2f32 + self.teleop_low.missed;
+        points / (points + missed_points)
+    }
+
+    /// every cargo the robot shot, made or not, per match
+    pub(super) fn cargo_per_match(&self) -> f32 {
+        let cargo = self.auto_high.attempts() + self.auto_low.attempts() + self.teleop_high.attempts() + self.teleop_low.attempts();
+        cargo / (self.matches as f32)
+    }
+
+    pub(super) fn tarmac_rate(&self) -> f32 {
+        self.left_tarmac / (self.matches as f32)
+    }
+
+    /// accuracy over every shot at either goal in either phase
+    pub(super) fn overall(&self) -> Option<f32> {
+        let mut all = GoalAccuracy::default();
+        for (_, _, goal) in self.by_goal() {
+            all += goal;
+        }
+        all.accuracy()
+    }
+
+    /// the four phase and goal combinations with their names, in the order they're charted
+    pub(super) fn by_goal(&self) -> [(&'static str, &'static str, GoalAccuracy); 4] {
+        [
+            ("Auto High", "AH", self.auto_high),
+            ("Auto Low", "AL", self.auto_low),
+            ("Teleop High", "TH", self.teleop_high),
+            ("Teleop Low", "TL", self.teleop_low),
+        ]
     }
 }
 
 impl AddAssign for AccuracyInfo {
     fn add_assign(&mut self, rhs: Self) {
-        self.points += rhs.points;
-        self.missed_points += rhs.missed_points;
+        self.matches += rhs.matches;
+        self.auto_high += rhs.auto_high;
+        self.auto_low += rhs.auto_low;
+        self.teleop_high += rhs.teleop_high;
+        self.teleop_low += rhs.teleop_low;
+        self.left_tarmac += rhs.left_tarmac;
     }
 }
 
 impl From<RobotMatchInfo> for AccuracyInfo {
     fn from(i: RobotMatchInfo) -> Self {
         AccuracyInfo {
-            points: i.teleop_high_made * 2 + i.teleop_low_made,
-            missed_points: i.teleop_high_missed * 2 + i.teleop_low_missed,
+            matches: 1,
+            auto_high: GoalAccuracy::new(i.auto_high_made, i.auto_high_missed),
+            auto_low: GoalAccuracy::new(i.auto_low_made, i.auto_low_missed),
+            teleop_high: GoalAccuracy::new(i.teleop_high_made, i.teleop_high_missed),
+            teleop_low: GoalAccuracy::new(i.teleop_low_made, i.teleop_low_missed),
+            left_tarmac: if matches!(i.left_tarmac, LeftTarmac::Yes) { 1f32 } else { 0f32 },
         }
     }
 }
 
-pub async fn get_accuracy_chart(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+impl MatchAverage for AccuracyInfo {
+    fn averaged(self, entries: u32) -> Self {
+        let scouts = entries as f32;
+        AccuracyInfo {
+            matches: self.matches / entries,
+            auto_high: self.auto_high.averaged(scouts),
+            auto_low: self.auto_low.averaged(scouts),
+            teleop_high: self.teleop_high.averaged(scouts),
+            teleop_low: self.teleop_low.averaged(scouts),
+            left_tarmac: self.left_tarmac / scouts,
+        }
+    }
+}
+
+/// load every entry and add them up by team, averaging matches scouted more than once
+async fn accuracy_by_team(pool: web::Data<DbPool>) -> HttpResult<BTreeMap<i32, AccuracyInfo>> {
     // could write more of this as an sql query
     let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
         use crate::schema::data::dsl::*;
@@ -40,43 +146,54 @@ pub async fn get_accuracy_chart(pool: web::Data<DbPool>) -> HttpResult<HttpRespo
         Ok(data.order_by(team.asc()).load(&conn)?)
     }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
 
+    Ok(by_team(infos))
+}
 
-    use charts::{Chart, VerticalBarView, ScaleBand, ScaleLinear, BarLabelPosition};
-    use std::cmp::Ordering;
+/// pick the value an accuracy chart is sorted by from the metric in the query
+fn accuracy_metric(metric: &str) -> fn(&AccuracyInfo) -> f32 {
+    match metric {
+        "overall" => |i| i.overall().unwrap_or(0f32),
+        "auto_high" => |i| i.auto_high.accuracy().unwrap_or(0f32),
+        "auto_low" => |i| i.auto_low.accuracy().unwrap_or(0f32),
+        "teleop_high" => |i| i.teleop_high.accuracy().unwrap_or(0f32),
+        "teleop_low" => |i| i.teleop_low.accuracy().unwrap_or(0f32),
+        "cargo" => AccuracyInfo::cargo_per_match,
+        "tarmac" => AccuracyInfo::tarmac_rate,
+        _ => AccuracyInfo::teleop_point_accuracy,
+    }
+}
 
-    let mut data: BTreeMap<i32, AccuracyInfo> = BTreeMap::new();
+pub async fn get_accuracy_chart(pool: web::Data<DbPool>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    let data = accuracy_by_team(pool).await?;
 
-    for info in infos {
-        *data.entry(info.team)
-            .or_insert(Default::default()) += info.into();
-    }
+    use charts::{Chart, VerticalBarView, ScaleBand, ScaleLinear, BarLabelPosition};
+
+    let data = match query.select(data, accuracy_metric(&query.metric)) {
+        Ok(data) => data,
+        Err(e) => return Ok(bad_query(e)),
+    };
 
-    // Define chart related sizes.
-    let width = 1300;
-    let height = 600;
-    let (top, right, bottom, left) = (90, 40, 50, 60);
+    // Define chart related sizes, leaving room for the team numbers to fit.
+    let (width, height) = query.size(1300, 600);
+    let labels: Vec<String> = data.iter().map(|(num, _)| format!("{}", num)).collect();
+    let layout = ChartLayout::for_bands(width, height, &labels);
 
     // Create a linear scale that will interpolate values in [0, 100] range to corresponding
     // values in [0, availableWidth] range (the width of the chart without the margins).
     let y = ScaleLinear::new()
         .set_domain(vec![0_f32, 1_f32])
-        .set_range(vec![height - top - bottom, 0]);
+        .set_range(vec![layout.inner_height(), 0]);
 
-    // have to convert to a vec to sort by points
-    let mut data: Vec<(i32, AccuracyInfo)> = data.into_iter().collect();
-    data.sort_by(|(_, a), (_, b)| a.accuracy().partial_cmp(&b.accuracy()).unwrap_or(Ordering::Equal));
-
-    // Create a band scale that maps team numbers to values in the [0, availableHeight]
-    // range (the height of the chart without the margins).
+    // Create a band scale that maps team numbers to values in the [0, availableWidth]
+    // range (the width of the chart without the margins).
     let x = ScaleBand::new()
-        .set_domain(
-            data.iter().map(|(num, _)| format!("{}", num)).collect())
-        .set_range(vec![0, height - top - bottom]);
+        .set_domain(labels)
+        .set_range(vec![0, layout.inner_width()]);
 
 
     let bar_data: Vec<(String, f32)> = data.into_iter()
         .map(|(team, info)| {
-            (format!("{}", team), info.accuracy())
+            (format!("{}", team), info.teleop_point_accuracy())
         })
         .collect();
 
@@ -85,29 +202,106 @@ pub async fn get_accuracy_chart(pool: web::Data<DbPool>) -> HttpResult<HttpRespo
         .set_x_scale(&x)
         .set_y_scale(&y)
         .set_label_position(BarLabelPosition::Center)
-        .load_data(&bar_data).unwrap();
+        .load_data(&bar_data).map_err(ErrorInternalServerError)?;
 
     // Generate and save the chart.
-    let svg_content = Chart::new()
+    let chart = Chart::new()
         .set_width(width)
         .set_height(height)
-        .set_margins(top, right, bottom, left)
+        .set_margins(layout.top, layout.right, layout.bottom, layout.left)
         .add_title(String::from("Teleop Accuracy"))
         .add_legend_at(charts::AxisPosition::Top)
-        .add_view(&view)
+        .add_view(view)
         .add_axis_bottom(&x)
         .add_axis_left(&y)
+        .set_bottom_axis_tick_label_rotation(layout.rotation)
         .add_left_axis_label("Accuracy Ratio")
-        .add_bottom_axis_label("Team")
-        .to_svg().unwrap();
+        .add_bottom_axis_label("Team");
+
+    query.respond(chart).await
+}
+
 
-    let document = svg::Document::new()
-        .set("width", width)
-        .set("height", height)
-        .set("viewBox", (0i32, 0i32, width, height))
-        .add(svg_content);
+pub struct TeamAccuracy {
+    team: i32,
+    info: AccuracyInfo,
+}
 
-    Ok(HttpResponse::Ok().content_type("image/svg+xml; charset=utf-8")
-            .body(document.to_string()))
+#[derive(Template)]
+#[template(path = "accuracy.html")]
+pub struct AccuracyTable {
+    teams: Vec<TeamAccuracy>,
 }
 
+/// accuracy for each phase and goal, plus how much cargo each team moves and how often they leave the tarmac
+pub async fn get_accuracy_table(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+    let data = accuracy_by_team(pool).await?;
+
+    let mut teams: Vec<TeamAccuracy> = data.into_iter()
+        .map(|(team, info)| TeamAccuracy { team, info })
+        .collect();
+    teams.sort_by(|a, b| b.info.cargo_per_match().partial_cmp(&a.info.cargo_per_match()).unwrap_or(Ordering::Equal));
+
+    let table = AccuracyTable { teams };
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(table.render().map_err(ErrorInternalServerError)?)
+    )
+}
+
+/// accuracy at each goal in each phase, with a bar for each next to each other for every team
+pub async fn get_accuracy_breakdown_chart(pool: web::Data<DbPool>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    let data = accuracy_by_team(pool).await?;
+
+    use charts::{Chart, VerticalBarView, ScaleBand, ScaleLinear, BarLabelPosition, BarMode};
+
+    let data = match query.select(data, accuracy_metric(&query.metric)) {
+        Ok(data) => data,
+        Err(e) => return Ok(bad_query(e)),
+    };
+
+    let categories: Vec<String> = data.iter().map(|(team, _)| team.to_string()).collect();
+    let bar_data: Vec<(String, f32, String)> = data.iter()
+        .flat_map(|(team, info)| info.by_goal().into_iter()
+            .map(move |(name, _, goal)| (team.to_string(), goal.accuracy().unwrap_or(0f32), name.to_string())))
+        .collect();
+
+    // Define chart related sizes, leaving room for the team numbers to fit.
+    let (width, height) = query.size(1300, 650);
+    let layout = ChartLayout::for_bands(width, height, &categories);
+
+    let y = ScaleLinear::new()
+        .set_domain(vec![0_f32, 1_f32])
+        .set_range(vec![layout.inner_height(), 0]);
+
+    let x = ScaleBand::new()
+        .set_domain(categories)
+        .set_range(vec![0, layout.inner_width()]);
+
+    // accuracies don't add up, so the goals of a team sit next to each other
+    let view = VerticalBarView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_bar_mode(BarMode::Grouped)
+        .set_group_padding(0.15)
+        .set_keys(AccuracyInfo::default().by_goal().iter().map(|(name, _, _)| name.to_string()).collect())
+        .set_label_position(BarLabelPosition::Center)
+        .set_label_visibility(false)
+        .load_data(&bar_data).map_err(ErrorInternalServerError)?;
+
+    let chart = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(layout.top, layout.right, layout.bottom, layout.left)
+        .add_title(String::from("Accuracy by Phase and Goal"))
+        .add_legend_at(charts::AxisPosition::Top)
+        .add_view(view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .set_bottom_axis_tick_label_rotation(layout.rotation)
+        .add_left_axis_label("Accuracy Ratio")
+        .add_bottom_axis_label("Team");
+
+    query.respond(chart).await
+}
@@ -0,0 +1,360 @@
+use std::{ops::AddAssign, collections::BTreeMap};
+use diesel::prelude::*;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use askama::Template;
+use serde::Deserialize;
+use crate::{HttpResult, models::RobotMatchInfo, DbPool, DatabaseError};
+use super::climb::ClimbInfo;
+use super::{MatchAverage, by_team};
+
+// cargo the alliance has to score for the cargo bonus, which drops when they get the quintet
+const CARGO_BONUS: usize = 20;
+const QUINTET_CARGO_BONUS: usize = 18;
+// cargo scored in auto for the quintet
+const QUINTET: usize = 5;
+const HANGAR_BONUS: usize = 16;
+const ALLIANCE_SIZE: usize = 3;
+
+/// Chance of every (auto cargo, total cargo) an alliance or robot could score,
+/// with anything past the thresholds piled into the last row or column
+#[derive(Clone)]
+struct CargoOdds([[f64; CARGO_BONUS + 1]; QUINTET + 1]);
+
+impl CargoOdds {
+    /// no robots always score nothing
+    fn nothing() -> Self {
+        let mut odds = [[0f64; CARGO_BONUS + 1]; QUINTET + 1];
+        odds[0][0] = 1f64;
+        CargoOdds(odds)
+    }
+
+    /// the distribution of the sum of two independent robots
+    fn combine(&self, other: &CargoOdds) -> CargoOdds {
+        let mut odds = [[0f64; CARGO_BONUS + 1]; QUINTET + 1];
+        for (a1, row1) in self.0.iter().enumerate() {
+            for (t1, p1) in row1.iter().enumerate().filter(|(_, p)| **p > 0f64) {
+                for (a2, row2) in other.0.iter().enumerate() {
+                    for (t2, p2) in row2.iter().enumerate() {
+                        odds[(a1 + a2).min(QUINTET)][(t1 + t2).min(CARGO_BONUS)] += p1 * p2;
+                    }
+                }
+            }
+        }
+        CargoOdds(odds)
+    }
+
+    fn quintet(&self) -> f64 {
+        self.0[QUINTET].iter().sum()
+    }
+
+    fn bonus(&self) -> f64 {
+        self.0.iter().enumerate()
+            .flat_map(|(auto, row)| row.iter().enumerate().map(move |(total, p)| (auto, total, p)))
+            .filter(|(auto, total, _)| *total >= CARGO_BONUS || (*auto >= QUINTET && *total >= QUINTET_CARGO_BONUS))
+            .map(|(_, _, p)| p)
+            .sum()
+    }
+}
+
+/// Chance of every number of hangar points, with anything past the bonus in the last entry
+#[derive(Clone)]
+struct HangarOdds([f64; HANGAR_BONUS + 1]);
+
+impl HangarOdds {
+    fn nothing() -> Self {
+        let mut odds = [0f64; HANGAR_BONUS + 1];
+        odds[0] = 1f64;
+        HangarOdds(odds)
+    }
+
+    fn combine(&self, other: &HangarOdds) -> HangarOdds {
+        let mut odds = [0f64; HANGAR_BONUS + 1];
+        for (h1, p1) in self.0.iter().enumerate() {
+            for (h2, p2) in other.0.iter().enumerate() {
+                odds[(h1 + h2).min(HANGAR_BONUS)] += p1 * p2;
+            }
+        }
+        HangarOdds(odds)
+    }
+
+    fn bonus(&self) -> f64 {
+        self.0[HANGAR_BONUS]
+    }
+}
+
+/// Everything a team has scored toward the bonuses, added up over its matches
+#[derive(Clone, Default)]
+pub(super) struct BonusInfo {
+    matches: u32,
+    // how many matches ended with each (auto cargo, total cargo),
+    // split between the outcomes when scouts disagreed about a match
+    cargo: BTreeMap<(usize, usize), f64>,
+    climb: ClimbInfo,
+}
+
+impl From<RobotMatchInfo> for BonusInfo {
+    fn from(i: RobotMatchInfo) -> Self {
+        // rows saved before validation can have negative counts
+        let auto = (i.auto_high_made + i.auto_low_made).max(0) as usize;
+        let total = auto + (i.teleop_high_made + i.teleop_low_made).max(0) as usize;
+        BonusInfo {
+            matches: 1,
+            cargo: BTreeMap::from([((auto, total), 1f64)]),
+            climb: i.into(),
+        }
+    }
+}
+
+impl AddAssign for BonusInfo {
+    fn add_assign(&mut self, rhs: Self) {
+        self.matches += rhs.matches;
+        for (score, count) in rhs.cargo {
+            *self.cargo.entry(score).or_default() += count;
+        }
+        self.climb += rhs.climb;
+    }
+}
+
+impl MatchAverage for BonusInfo {
+    fn averaged(self, entries: u32) -> Self {
+        BonusInfo {
+            matches: self.matches / entries,
+            cargo: self.cargo.into_iter().map(|(score, count)| (score, count / entries as f64)).collect(),
+            climb: self.climb.averaged(entries),
+        }
+    }
+}
+
+impl BonusInfo {
+    fn cargo_odds(&self) -> CargoOdds {
+        let mut odds = [[0f64; CARGO_BONUS + 1]; QUINTET + 1];
+        for ((auto, total), count) in self.cargo.iter() {
+            odds[(*auto).min(QUINTET)][(*total).min(CARGO_BONUS)] += count / self.matches as f64;
+        }
+        CargoOdds(odds)
+    }
+
+    fn hangar_odds(&self) -> HangarOdds {
+        let mut odds = [0f64; HANGAR_BONUS + 1];
+        for (points, p) in self.climb.hangar_points() {
+            odds[points.min(HANGAR_BONUS)] += p;
+        }
+        HangarOdds(odds)
+    }
+
+    fn mean_auto_cargo(&self) -> f64 {
+        self.cargo.iter().map(|((auto, _), count)| *auto as f64 * count).sum::<f64>() / self.matches as f64
+    }
+
+    fn mean_cargo(&self) -> f64 {
+        self.cargo.iter().map(|((_, total), count)| *total as f64 * count).sum::<f64>() / self.matches as f64
+    }
+
+    fn mean_hangar(&self) -> f64 {
+        self.climb.hangar_points().iter().map(|(points, p)| *points as f64 * p).sum()
+    }
+}
+
+/// What one team adds to the alliance
+pub struct Contribution {
+    team: i32,
+    matches: u32,
+    auto_cargo: f64,
+    cargo: f64,
+    hangar: f64,
+}
+
+/// Chance of each bonus for the whole alliance
+pub struct BonusOdds {
+    quintet: f64,
+    cargo: f64,
+    hangar: f64,
+}
+
+#[derive(Deserialize)]
+pub struct PlanQuery {
+    #[serde(default)]
+    teams: String,
+}
+
+#[derive(Template)]
+#[template(path = "plan.html")]
+pub struct PlanPage {
+    teams_input: String,
+    error: Option<String>,
+    contributions: Vec<Contribution>,
+    // asked for but never scouted, so left out of the odds
+    unscouted: Vec<i32>,
+    odds: Option<BonusOdds>,
+}
+
+impl PlanPage {
+    fn percent(&self, p: &f64) -> String {
+        format!("{:.0}%", p * 100f64)
+    }
+
+    /// what the scouted teams add up to on average, as (auto cargo, cargo, hangar points)
+    fn totals(&self) -> (f64, f64, f64) {
+        self.contributions.iter().fold((0f64, 0f64, 0f64), |(a, c, h), t| (a + t.auto_cargo, c + t.cargo, h + t.hangar))
+    }
+}
+
+/// estimate the chance an alliance gets each bonus ranking point from how its teams have played so far,
+/// treating the robots as independent and each one as likely to repeat any match it's already played
+pub async fn get_match_plan(pool: web::Data<DbPool>, query: web::Query<PlanQuery>) -> HttpResult<HttpResponse> {
+    let teams_input = query.into_inner().teams;
+    let mut page = PlanPage {
+        teams_input: teams_input.clone(),
+        error: None,
+        contributions: Vec::new(),
+        unscouted: Vec::new(),
+        odds: None,
+    };
+
+    let numbers = match super::parse_teams(&teams_input, ALLIANCE_SIZE) {
+        Ok(numbers) => numbers,
+        Err(e) => {
+            page.error = Some(e);
+            Vec::new()
+        },
+    };
+
+    if !numbers.is_empty() {
+        let wanted = numbers.clone();
+        let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+            use crate::schema::data::dsl::*;
+            let conn = pool.get()?;
+            Ok(data.filter(team.eq_any(wanted)).load(&conn)?)
+        }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+        let teams: BTreeMap<i32, BonusInfo> = by_team(infos);
+
+        let mut cargo = CargoOdds::nothing();
+        let mut hangar = HangarOdds::nothing();
+        for number in numbers {
+            match teams.get(&number) {
+                Some(info) => {
+                    cargo = cargo.combine(&info.cargo_odds());
+                    hangar = hangar.combine(&info.hangar_odds());
+                    page.contributions.push(Contribution {
+                        team: number,
+                        matches: info.matches,
+                        auto_cargo: info.mean_auto_cargo(),
+                        cargo: info.mean_cargo(),
+                        hangar: info.mean_hangar(),
+                    });
+                },
+                None => page.unscouted.push(number),
+            }
+        }
+
+        if !page.contributions.is_empty() {
+            page.odds = Some(BonusOdds {
+                quintet: cargo.quintet(),
+                cargo: cargo.bonus(),
+                hangar: hangar.bonus(),
+            });
+        }
+    }
+
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(page.render().map_err(ErrorInternalServerError)?)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::Climb;
+    use crate::analysis::tests::entry;
+
+    /// a robot that always ends up with the same cargo
+    fn always(auto: usize, total: usize) -> CargoOdds {
+        let mut odds = [[0f64; CARGO_BONUS + 1]; QUINTET + 1];
+        odds[auto][total] = 1f64;
+        CargoOdds(odds)
+    }
+
+    fn climbs(points: &[(usize, f64)]) -> HangarOdds {
+        let mut odds = [0f64; HANGAR_BONUS + 1];
+        for (points, p) in points {
+            odds[*points] += p;
+        }
+        HangarOdds(odds)
+    }
+
+    fn total(odds: &CargoOdds) -> f64 {
+        odds.0.iter().flatten().sum()
+    }
+
+    #[test]
+    fn nothing_gets_no_bonus() {
+        assert_eq!(CargoOdds::nothing().quintet(), 0f64);
+        assert_eq!(CargoOdds::nothing().bonus(), 0f64);
+        assert_eq!(HangarOdds::nothing().bonus(), 0f64);
+    }
+
+    #[test]
+    fn combining_with_nothing_changes_nothing() {
+        let odds = CargoOdds::nothing().combine(&always(2, 7));
+        assert_eq!(odds.0[2][7], 1f64);
+        assert_eq!(total(&odds), 1f64);
+    }
+
+    #[test]
+    fn cargo_past_the_thresholds_piles_up_at_the_edge() {
+        let odds = always(4, 15).combine(&always(4, 15));
+        assert_eq!(odds.0[QUINTET][CARGO_BONUS], 1f64);
+        assert_eq!(odds.quintet(), 1f64);
+        assert_eq!(odds.bonus(), 1f64);
+    }
+
+    #[test]
+    fn the_quintet_lowers_the_cargo_bonus() {
+        assert_eq!(always(3, 9).combine(&always(2, 9)).bonus(), 1f64);
+        assert_eq!(always(2, 9).combine(&always(2, 9)).bonus(), 0f64);
+        assert_eq!(always(2, 10).combine(&always(2, 10)).bonus(), 1f64);
+    }
+
+    #[test]
+    fn independent_robots_multiply() {
+        let mut coin = [[0f64; CARGO_BONUS + 1]; QUINTET + 1];
+        coin[0][0] = 0.5;
+        coin[0][10] = 0.5;
+        let odds = CargoOdds(coin).combine(&CargoOdds(coin));
+        assert_eq!(odds.bonus(), 0.25);
+        assert!((total(&odds) - 1f64).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hangar_bonus_needs_sixteen_points() {
+        assert_eq!(climbs(&[(15, 1.0)]).combine(&climbs(&[(4, 1.0)])).bonus(), 1f64);
+        assert_eq!(climbs(&[(10, 1.0)]).combine(&climbs(&[(4, 1.0)])).bonus(), 0f64);
+        assert_eq!(climbs(&[(10, 0.5), (0, 0.5)]).combine(&climbs(&[(6, 1.0)])).bonus(), 0.5);
+    }
+
+    #[test]
+    fn co_scouted_matches_split_the_odds() {
+        let teams: BTreeMap<i32, BonusInfo> = by_team(vec![
+            entry(33, 1, "Sam", 10, Climb::High, None),
+            entry(33, 1, "Alex", 12, Climb::Mid, None),
+            entry(33, 2, "Sam", 10, Climb::High, None),
+        ]);
+        let info = &teams[&33];
+        assert_eq!(info.matches, 2);
+        assert_eq!(info.cargo_odds().0[0][10], 0.75);
+        assert!((total(&info.cargo_odds()) - 1f64).abs() < 1e-9);
+        assert_eq!(info.hangar_odds().0[10], 0.75);
+        assert_eq!(info.mean_cargo(), 10.5);
+    }
+
+    #[test]
+    fn negative_counts_score_no_cargo() {
+        let mut info = entry(33, 1, "Sam", -5, Climb::No, None);
+        info.auto_low_made = -3;
+        let info = BonusInfo::from(info);
+        assert_eq!(info.cargo.keys().collect::<Vec<_>>(), vec![&(0, 0)]);
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+use diesel::prelude::*;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use charts::{Chart, ChartError, Color, Grid, Page, ScaleBand, ScaleLinear, VerticalBarView};
+use crate::{HttpResult, models::RobotMatchInfo, DbPool, DatabaseError};
+use super::{accuracy::AccuracyInfo, climb::ClimbInfo, points::TeamPointsInfo, by_match, by_team};
+use super::options::ChartQuery;
+
+// space the page and grid take up around the charts
+const CARD_PADDING: isize = 60;
+const CARD_HEADER: isize = 80;
+// the accuracy and climb charts have a single unlabeled series each, so they get colors
+// the shared legend doesn't use rather than looking like the "Auto" bars
+const ACCURACY_COLOR: &str = "#9467bd";
+const CLIMB_COLOR: &str = "#8c564b";
+
+/// one bar chart on the card, with a bar per label stacked by the keys in the data
+fn bar_chart(title: &str, y_label: &str, (width, height): (isize, isize), colors: Vec<Color>, labels: Vec<String>, data: Vec<(String, f32, String)>) -> Result<Chart, ChartError> {
+    let (top, right, bottom, left) = (50, 20, 50, 55);
+    let mut totals: BTreeMap<&str, f32> = BTreeMap::new();
+    for (label, value, _) in data.iter() {
+        *totals.entry(label.as_str()).or_default() += value;
+    }
+    let max = totals.values().copied().fold(1f32, f32::max);
+
+    let x = ScaleBand::new()
+        .set_domain(labels)
+        .set_range(vec![0, width - left - right]);
+    let y = ScaleLinear::new()
+        .set_domain(vec![0f32, max])
+        .set_range(vec![height - top - bottom, 0]);
+    let view = VerticalBarView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_label_visibility(false)
+        .set_colors(colors)
+        .load_data(&data)?;
+
+    Ok(Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(title.to_string())
+        .add_view(view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label(y_label))
+}
+
+/// everything about a team on one image, for sharing or printing
+pub async fn get_team_card(pool: web::Data<DbPool>, path: web::Path<i32>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    let team_number = path.into_inner();
+    let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.filter(team.eq(team_number)).load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    // matches scouted more than once get averaged
+    let mut cargo: BTreeMap<i32, (f32, f32, f32)> = BTreeMap::new();
+    for info in infos.iter() {
+        let entry = cargo.entry(info.match_number).or_default();
+        entry.0 += 1f32;
+        entry.1 += (info.auto_high_made + info.auto_low_made) as f32;
+        entry.2 += (info.teleop_high_made + info.teleop_low_made) as f32;
+    }
+    let points: BTreeMap<i32, TeamPointsInfo> = by_match(infos.iter().cloned()).into_iter()
+        .map(|((_, match_number), points)| (match_number, points))
+        .collect();
+    let accuracy: AccuracyInfo = by_team(infos.iter().cloned()).remove(&team_number).unwrap_or_default();
+    let climb: ClimbInfo = by_team(infos).remove(&team_number).unwrap_or_default();
+
+    let (width, height) = query.size(1100, 800);
+    let cell = ((width - CARD_PADDING) / 2, (height - CARD_PADDING - CARD_HEADER) / 2);
+    let matches: Vec<String> = points.keys().map(|m| m.to_string()).collect();
+
+    let points_chart = bar_chart("Points per Match", "Points", cell, Color::color_scheme_10(), matches.clone(), points.iter()
+        .flat_map(|(m, p)| [
+            (m.to_string(), p.mean_auto(), "Auto".to_string()),
+            (m.to_string(), p.mean_tele(), "TeleOp".to_string()),
+            (m.to_string(), p.mean_climb(), "Climb".to_string()),
+        ])
+        .collect()).map_err(ErrorInternalServerError)?;
+
+    let cargo_chart = bar_chart("Cargo Scored per Match", "Cargo", cell, Color::color_scheme_10(), matches, cargo.iter()
+        .flat_map(|(m, (entries, auto, teleop))| [
+            (m.to_string(), auto / entries, "Auto".to_string()),
+            (m.to_string(), teleop / entries, "TeleOp".to_string()),
+        ])
+        .collect()).map_err(ErrorInternalServerError)?;
+
+    let goals = accuracy.by_goal();
+    let accuracy_chart = bar_chart("Accuracy by Goal", "Percent Made", cell,
+        Color::from_vec_of_hex_strings(vec![ACCURACY_COLOR]),
+        goals.iter().map(|(name, _, _)| name.to_string()).collect(),
+        goals.iter().map(|(name, _, goal)| (name.to_string(), goal.accuracy().unwrap_or(0f32) * 100f32, String::new())).collect(),
+    ).map_err(ErrorInternalServerError)?;
+
+    let levels = [
+        ("None", climb.no_attempts),
+        ("Failed", climb.fails),
+        ("Low", climb.low_climbs),
+        ("Mid", climb.mid_climbs),
+        ("High", climb.high_climbs),
+        ("Traversal", climb.traverse_climbs),
+    ];
+    let climb_chart = bar_chart("Climbs", "Matches", cell,
+        Color::from_vec_of_hex_strings(vec![CLIMB_COLOR]),
+        levels.iter().map(|(level, _)| level.to_string()).collect(),
+        levels.iter().map(|(level, count)| (level.to_string(), *count, String::new())).collect(),
+    ).map_err(ErrorInternalServerError)?;
+
+    let page = Page::new()
+        .add_title(format!("Team {} Report Card", team_number))
+        .add_grid(Grid::new(2)
+            .add_legend()
+            .add_chart(points_chart)
+            .add_chart(cargo_chart)
+            .add_chart(accuracy_chart)
+            .add_chart(climb_chart));
+
+    query.respond_page(page).await
+}
@@ -1,27 +1,30 @@
-use std::{ops::{Add, AddAssign}, collections::BTreeMap};
+use std::{ops::AddAssign, collections::BTreeMap};
 use diesel::prelude::*;
 use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
 use crate::{HttpResult, models::RobotMatchInfo, DbPool, DatabaseError, schema::Climb};
+use super::options::{ChartQuery, ChartLayout, bad_query};
+use super::{MatchAverage, by_team};
 
 #[derive(Clone, Default)]
-struct ClimbInfo {
-    no_attempts: u32,
-    fails: u32,
-    low_climbs: u32,
-    mid_climbs: u32,
-    high_climbs: u32,
-    traverse_climbs: u32,
+pub(super) struct ClimbInfo {
+    pub(super) no_attempts: f32,
+    pub(super) fails: f32,
+    pub(super) low_climbs: f32,
+    pub(super) mid_climbs: f32,
+    pub(super) high_climbs: f32,
+    pub(super) traverse_climbs: f32,
 }
 
 impl From<RobotMatchInfo> for ClimbInfo {
     fn from(i: RobotMatchInfo) -> Self {
+        let count = |hit: bool| if hit { 1f32 } else { 0f32 };
         ClimbInfo {
-            no_attempts: matches!(i.climb, Climb::No) as u32,
-            fails: matches!(i.climb, Climb::Failed) as u32,
-            low_climbs: matches!(i.climb, Climb::Low) as u32,
-            mid_climbs: matches!(i.climb, Climb::Mid) as u32,
-            high_climbs: matches!(i.climb, Climb::High) as u32,
-            traverse_climbs: matches!(i.climb, Climb::Traversal) as u32,
+            no_attempts: count(matches!(i.climb, Climb::No)),
+            fails: count(matches!(i.climb, Climb::Failed)),
+            low_climbs: count(matches!(i.climb, Climb::Low)),
+            mid_climbs: count(matches!(i.climb, Climb::Mid)),
+            high_climbs: count(matches!(i.climb, Climb::High)),
+            traverse_climbs: count(matches!(i.climb, Climb::Traversal)),
         }
     }
 }
@@ -37,23 +40,61 @@ impl AddAssign for ClimbInfo {
     }
 }
 
+impl MatchAverage for ClimbInfo {
+    fn averaged(self, entries: u32) -> Self {
+        let entries = entries as f32;
+        ClimbInfo {
+            no_attempts: self.no_attempts / entries,
+            fails: self.fails / entries,
+            low_climbs: self.low_climbs / entries,
+            mid_climbs: self.mid_climbs / entries,
+            high_climbs: self.high_climbs / entries,
+            traverse_climbs: self.traverse_climbs / entries,
+        }
+    }
+}
+
 impl ClimbInfo {
-    fn total(&self) -> u32 {
+    pub(super) fn total(&self) -> f32 {
         self.no_attempts + self.fails + self.low_climbs + self.mid_climbs + self.high_climbs + self.traverse_climbs
     }
 
     fn mean_points(&self) -> f32 {
         (
-            self.low_climbs as f32 * 4f32 +
-            self.mid_climbs as f32 * 6f32 +
-            self.high_climbs as f32 * 10f32 +
-            self.traverse_climbs as f32 * 15f32
+            self.low_climbs * 4f32 +
+            self.mid_climbs * 6f32 +
+            self.high_climbs * 10f32 +
+            self.traverse_climbs * 15f32
         )
-            / (self.total() as f32)
+            / self.total()
+    }
+
+    /// how often each number of hangar points came up, as (points, fraction of matches)
+    pub(super) fn hangar_points(&self) -> [(usize, f64); 5] {
+        let total = self.total() as f64;
+        [
+            (0, (self.no_attempts + self.fails) as f64 / total),
+            (4, self.low_climbs as f64 / total),
+            (6, self.mid_climbs as f64 / total),
+            (10, self.high_climbs as f64 / total),
+            (15, self.traverse_climbs as f64 / total),
+        ]
     }
 }
 
-pub async fn get_climb_chart(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+/// pick the value a climb chart is sorted by from the metric in the query
+fn climb_metric(metric: &str) -> fn(&ClimbInfo) -> f32 {
+    match metric {
+        "traversal" => |i| i.traverse_climbs / i.total(),
+        "high" => |i| i.high_climbs / i.total(),
+        "mid" => |i| i.mid_climbs / i.total(),
+        "low" => |i| i.low_climbs / i.total(),
+        "failed" => |i| i.fails / i.total(),
+        _ => ClimbInfo::mean_points,
+    }
+}
+
+pub async fn get_climb_chart(pool: web::Data<DbPool>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
     // could write more of this as an sql query
     let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
         use crate::schema::data::dsl::*;
@@ -62,47 +103,43 @@ pub async fn get_climb_chart(pool: web::Data<DbPool>) -> HttpResult<HttpResponse
     }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
 
 
-    use charts::{Chart, VerticalBarView, ScaleBand, ScaleLinear, BarLabelPosition};
-    use std::cmp::Ordering;
+    use charts::{Chart, VerticalBarView, ScaleBand, ScaleLinear, BarLabelPosition, BarMode};
 
-    let mut data: BTreeMap<i32, ClimbInfo> = BTreeMap::new();
+    let data: BTreeMap<i32, ClimbInfo> = by_team(infos);
 
-    for info in infos {
-        *data.entry(info.team)
-            .or_insert(Default::default()) += info.into();
-    }
+    let data = match query.select(data, climb_metric(&query.metric)) {
+        Ok(data) => data,
+        Err(e) => return Ok(bad_query(e)),
+    };
 
-    // Define chart related sizes.
-    let width = 1300;
-    let height = 600;
-    let (top, right, bottom, left) = (90, 40, 50, 60);
+    // Define chart related sizes, leaving room for the team numbers to fit.
+    let (width, height) = query.size(1300, 600);
+    let labels: Vec<String> = data.iter().map(|(num, _)| format!("{}", num)).collect();
+    let layout = ChartLayout::for_bands(width, height, &labels);
 
     // Create a linear scale that will interpolate values in [0, 100] range to corresponding
     // values in [0, availableWidth] range (the width of the chart without the margins).
     let y = ScaleLinear::new()
         .set_domain(vec![0_f32, 1_f32])
-        .set_range(vec![height - top - bottom, 0]);
-
-    // have to convert to a vec to sort by points
-    let mut data: Vec<(i32, ClimbInfo)> = data.into_iter().collect();
-    data.sort_by(|(_, a), (_, b)| a.mean_points().partial_cmp(&b.mean_points()).unwrap_or(Ordering::Equal));
+        .set_range(vec![layout.inner_height(), 0]);
 
-    // Create a band scale that maps team numbers to values in the [0, availableHeight]
-    // range (the height of the chart without the margins).
+    // Create a band scale that maps team numbers to values in the [0, availableWidth]
+    // range (the width of the chart without the margins).
     let x = ScaleBand::new()
-        .set_domain(
-            data.iter().map(|(num, _)| format!("{}", num)).collect())
-        .set_range(vec![0, height - top - bottom]);
+        .set_domain(labels)
+        .set_range(vec![0, layout.inner_width()]);
 
 
+    // the view turns the counts into proportions of the team's matches
     let bar_data: Vec<(String, f32, String)> = data.into_iter()
         .flat_map(|(team, info)| {
             [
-                (format!("{}", team), (info.fails as f32) / (info.total() as f32), "Failed Climb".to_string()),
-                (format!("{}", team), (info.low_climbs as f32) / (info.total() as f32), "Low".to_string()),
-                (format!("{}", team), (info.mid_climbs as f32) / (info.total() as f32), "Mid".to_string()),
-                (format!("{}", team), (info.high_climbs as f32) / (info.total() as f32), "High".to_string()),
-                (format!("{}", team), (info.traverse_climbs as f32) / (info.total() as f32), "Traverse".to_string()),
+                (format!("{}", team), info.fails, "Failed Climb".to_string()),
+                (format!("{}", team), info.low_climbs, "Low".to_string()),
+                (format!("{}", team), info.mid_climbs, "Mid".to_string()),
+                (format!("{}", team), info.high_climbs, "High".to_string()),
+                (format!("{}", team), info.traverse_climbs, "Traverse".to_string()),
+                (format!("{}", team), info.no_attempts, "No Climb".to_string()),
             ]
         })
         .collect();
@@ -111,29 +148,66 @@ pub async fn get_climb_chart(pool: web::Data<DbPool>) -> HttpResult<HttpResponse
     let view = VerticalBarView::new()
         .set_x_scale(&x)
         .set_y_scale(&y)
+        .set_bar_mode(BarMode::Normalized)
+        .set_label_rounding_precision(2)
         .set_label_position(BarLabelPosition::Center)
-        .load_data(&bar_data).unwrap();
+        .load_data(&bar_data).map_err(ErrorInternalServerError)?;
 
     // Generate and save the chart.
-    let svg_content = Chart::new()
+    let chart = Chart::new()
         .set_width(width)
         .set_height(height)
-        .set_margins(top, right, bottom, left)
+        .set_margins(layout.top, layout.right, layout.bottom, layout.left)
         .add_title(String::from("Climb"))
         .add_legend_at(charts::AxisPosition::Top)
-        .add_view(&view)
+        .add_view(view)
         .add_axis_bottom(&x)
         .add_axis_left(&y)
+        .set_bottom_axis_tick_label_rotation(layout.rotation)
         .add_left_axis_label("Proportion")
-        .add_bottom_axis_label("Team")
-        .to_svg().unwrap();
+        .add_bottom_axis_label("Team");
+
+    query.respond(chart).await
+}
 
-    let document = svg::Document::new()
-        .set("width", width)
-        .set("height", height)
-        .set("viewBox", (0i32, 0i32, width, height))
-        .add(svg_content);
+/// how one team's matches ended, as a donut for the drive coach
+pub async fn get_team_climb_donut(pool: web::Data<DbPool>, path: web::Path<i32>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    let team_number = path.into_inner();
+    let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.filter(team.eq(team_number)).load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    use charts::{Chart, DonutView};
+
+    let info: ClimbInfo = by_team(infos).remove(&team_number).unwrap_or_default();
+
+    let (width, height) = query.size(600, 450);
+    let (top, right, bottom, left) = (90, 20, 20, 20);
+
+    // keep the colors in the same order as the climb chart so outcomes match across pages
+    let donut_data = vec![
+        ("Failed Climb", info.fails),
+        ("Low", info.low_climbs),
+        ("Mid", info.mid_climbs),
+        ("High", info.high_climbs),
+        ("Traverse", info.traverse_climbs),
+        ("No Climb", info.no_attempts),
+    ];
+
+    let view = DonutView::new()
+        .set_center_label(format!("{:.0} matches", info.total()))
+        .load_data(&donut_data).map_err(ErrorInternalServerError)?;
+
+    // no axes, the slices speak for themselves
+    let chart = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(format!("Team {} Climb Outcomes", team_number))
+        .add_legend_at(charts::AxisPosition::Top)
+        .add_view(view);
 
-    Ok(HttpResponse::Ok().content_type("image/svg+xml; charset=utf-8")
-            .body(document.to_string()))
+    query.respond(chart).await
 }
@@ -0,0 +1,430 @@
+use std::{ops::AddAssign, collections::BTreeMap};
+use diesel::prelude::*;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use askama::Template;
+use serde::Deserialize;
+use crate::{HttpResult, models::{RobotMatchInfo, MatchEvent}, DbPool, DatabaseError, validation::RATINGS};
+use super::{points::{TeamPointsInfo, match_points}, accuracy::AccuracyInfo, climb::ClimbInfo, ratings::RatingsInfo};
+use super::timing::{timing_by_team, show_seconds};
+use super::options::{ChartQuery, bad_query};
+use super::{MatchAverage, by_match, by_team};
+
+// more than this and the lines and radar get too crowded to read
+const MAX_COMPARED: usize = 6;
+const RADAR_AXES: [&str; 5] = ["Auto", "Teleop", "Climb", "Accuracy", "Consistency"];
+// (metric query value, chart title)
+const MATCH_METRICS: [(&str, &str); 4] = [
+    ("total", "Points"),
+    ("auto", "Auto Points"),
+    ("teleop", "Teleop Points"),
+    ("climb", "Climb Points"),
+];
+
+/// Everything the comparison shows for a team, added up over its matches
+#[derive(Clone, Default)]
+pub(super) struct CompareInfo {
+    pub(super) points: TeamPointsInfo,
+    pub(super) accuracy: AccuracyInfo,
+    pub(super) climb: ClimbInfo,
+    pub(super) ratings: RatingsInfo,
+    match_totals: Vec<f32>,
+}
+
+impl From<RobotMatchInfo> for CompareInfo {
+    fn from(i: RobotMatchInfo) -> Self {
+        CompareInfo {
+            match_totals: vec![match_points(&i) as f32],
+            points: i.clone().into(),
+            accuracy: i.clone().into(),
+            climb: i.clone().into(),
+            ratings: i.into(),
+        }
+    }
+}
+
+impl AddAssign for CompareInfo {
+    fn add_assign(&mut self, rhs: Self) {
+        self.points += rhs.points;
+        self.accuracy += rhs.accuracy;
+        self.climb += rhs.climb;
+        self.ratings += rhs.ratings;
+        self.match_totals.extend(rhs.match_totals);
+    }
+}
+
+impl MatchAverage for CompareInfo {
+    fn averaged(self, entries: u32) -> Self {
+        let total = self.match_totals.iter().sum::<f32>();
+        CompareInfo {
+            points: self.points.averaged(entries),
+            accuracy: self.accuracy.averaged(entries),
+            climb: self.climb.averaged(entries),
+            ratings: self.ratings.averaged(entries),
+            match_totals: vec![total / entries as f32],
+        }
+    }
+}
+
+impl CompareInfo {
+    fn points_std_dev(&self) -> Option<f32> {
+        if self.match_totals.len() < 2 {
+            return None;
+        }
+        let n = self.match_totals.len() as f32;
+        let mean = self.match_totals.iter().sum::<f32>() / n;
+        let variance = self.match_totals.iter().map(|t| (t - mean).powi(2)).sum::<f32>() / (n - 1f32);
+        Some(variance.sqrt())
+    }
+
+    /// 1 for a team that scores the same every match, shrinking as the spread grows compared to the mean
+    fn consistency(&self) -> Option<f32> {
+        let mean = self.points.mean_total();
+        match self.points_std_dev() {
+            Some(sd) if mean > 0f32 => Some(1f32 / (1f32 + sd / mean)),
+            _ => None,
+        }
+    }
+
+    /// the values along each of the radar axes, before they're scaled against the rest of the event
+    fn radar_values(&self) -> [f32; 5] {
+        [
+            self.points.mean_auto(),
+            self.points.mean_tele(),
+            self.points.mean_climb(),
+            self.accuracy.overall().unwrap_or(0f32),
+            self.consistency().unwrap_or(0f32),
+        ]
+    }
+
+    fn climb_share(&self, count: f32) -> String {
+        format!("{:.0}%", count / self.climb.total() * 100f32)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CompareQuery {
+    #[serde(default)]
+    teams: String,
+}
+
+/// One line of the comparison table, with a cell for each team
+pub struct MetricRow {
+    label: String,
+    cells: Vec<String>,
+}
+
+impl MetricRow {
+    fn new(label: &str, infos: &[CompareInfo], cell: impl Fn(&CompareInfo) -> String) -> Self {
+        MetricRow { label: label.to_string(), cells: infos.iter().map(cell).collect() }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "compare.html")]
+pub struct ComparePage {
+    teams_input: String,
+    error: Option<String>,
+    // the teams that have data, as they go in chart urls
+    teams_param: String,
+    teams: Vec<i32>,
+    unscouted: Vec<i32>,
+    rows: Vec<MetricRow>,
+}
+
+impl ComparePage {
+    fn metrics(&self) -> &[(&str, &str)] {
+        &MATCH_METRICS
+    }
+}
+
+/// load and add up everyone's data, since the radar scales each team against the whole event
+async fn compare_by_team(pool: web::Data<DbPool>) -> HttpResult<BTreeMap<i32, CompareInfo>> {
+    let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    Ok(by_team(infos))
+}
+
+/// side by side statistics for a few teams, with charts of them against each other
+pub async fn get_comparison(pool: web::Data<DbPool>, query: web::Query<CompareQuery>) -> HttpResult<HttpResponse> {
+    let teams_input = query.into_inner().teams;
+    let mut page = ComparePage {
+        teams_input: teams_input.clone(),
+        error: None,
+        teams_param: String::new(),
+        teams: Vec::new(),
+        unscouted: Vec::new(),
+        rows: Vec::new(),
+    };
+
+    let numbers = match super::parse_teams(&teams_input, MAX_COMPARED) {
+        Ok(numbers) => numbers,
+        Err(e) => {
+            page.error = Some(e);
+            Vec::new()
+        },
+    };
+
+    if !numbers.is_empty() {
+        let events_pool = pool.clone();
+        let data = compare_by_team(pool).await?;
+        let events: Vec<MatchEvent> = web::block(move || -> Result<Vec<MatchEvent>, DatabaseError> {
+            use crate::schema::match_events::dsl::*;
+            let conn = events_pool.get()?;
+            Ok(match_events.load(&conn)?)
+        }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+        let timing = timing_by_team(&events);
+
+        let mut infos = Vec::new();
+        for number in numbers {
+            match data.get(&number) {
+                Some(info) => {
+                    page.teams.push(number);
+                    infos.push(info.clone());
+                },
+                None => page.unscouted.push(number),
+            }
+        }
+        page.teams_param = page.teams.iter().map(|t| t.to_string()).collect::<Vec<_>>().join(",");
+        let timings: Vec<_> = page.teams.iter().map(|t| timing.get(t).cloned().unwrap_or_default()).collect();
+
+        let mut rows = vec![
+            MetricRow::new("Matches", &infos, |i| i.points.num_matches.to_string()),
+            MetricRow::new("Points", &infos, |i| format!("{:.1}", i.points.mean_total())),
+            MetricRow::new("Auto Points", &infos, |i| format!("{:.1}", i.points.mean_auto())),
+            MetricRow::new("Teleop Points", &infos, |i| format!("{:.1}", i.points.mean_tele())),
+            MetricRow::new("Climb Points", &infos, |i| format!("{:.1}", i.points.mean_climb())),
+            MetricRow::new("Points Std Dev", &infos, |i| i.points_std_dev().map(|sd| format!("{:.1}", sd)).unwrap_or_else(|| "–".to_string())),
+            MetricRow::new("Consistency", &infos, |i| i.consistency().map(|c| format!("{:.2}", c)).unwrap_or_else(|| "–".to_string())),
+            MetricRow::new("Auto High", &infos, |i| i.accuracy.auto_high.show()),
+            MetricRow::new("Auto Low", &infos, |i| i.accuracy.auto_low.show()),
+            MetricRow::new("Teleop High", &infos, |i| i.accuracy.teleop_high.show()),
+            MetricRow::new("Teleop Low", &infos, |i| i.accuracy.teleop_low.show()),
+            MetricRow::new("Cargo per Match", &infos, |i| format!("{:.1}", i.accuracy.cargo_per_match())),
+            MetricRow::new("Left Tarmac", &infos, |i| format!("{:.0}%", i.accuracy.tarmac_rate() * 100f32)),
+            MetricRow::new("Traversal Climbs", &infos, |i| i.climb_share(i.climb.traverse_climbs)),
+            MetricRow::new("High Climbs", &infos, |i| i.climb_share(i.climb.high_climbs)),
+            MetricRow::new("Mid Climbs", &infos, |i| i.climb_share(i.climb.mid_climbs)),
+            MetricRow::new("Low Climbs", &infos, |i| i.climb_share(i.climb.low_climbs)),
+            MetricRow::new("Failed Climbs", &infos, |i| i.climb_share(i.climb.fails)),
+        ];
+        for (field, label, _) in RATINGS.iter() {
+            rows.push(MetricRow::new(label, &infos, |i| i.ratings.get(field).show()));
+        }
+        rows.push(MetricRow { label: "Cycle Time".to_string(), cells: timings.iter().map(|t| show_seconds(t.mean_cycle_time())).collect() });
+        rows.push(MetricRow { label: "Climb Duration".to_string(), cells: timings.iter().map(|t| show_seconds(t.mean_climb_duration())).collect() });
+        page.rows = rows;
+    }
+
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(page.render().map_err(ErrorInternalServerError)?)
+    )
+}
+
+/// points in each match for a few teams, with a line for each team, the metric picks which points
+pub async fn get_comparison_matches_chart(pool: web::Data<DbPool>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    let numbers = match super::parse_teams(&query.teams, MAX_COMPARED) {
+        Ok(numbers) => numbers,
+        Err(e) => return Ok(bad_query(e)),
+    };
+    let (metric, title) = MATCH_METRICS.iter()
+        .find(|(m, _)| *m == query.metric)
+        .copied()
+        .unwrap_or(MATCH_METRICS[0]);
+
+    let wanted = numbers.clone();
+    let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.filter(team.eq_any(wanted)).load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    // matches scouted more than once get averaged
+    let matches: BTreeMap<(i32, i32), TeamPointsInfo> = by_match(infos);
+
+    use charts::{Chart, LineSeriesView, ScaleLinear, MarkerType};
+    use std::cmp::Ordering;
+
+    let line_data: Vec<(f32, f32, String)> = matches.iter()
+        .map(|((team, match_number), points)| {
+            let value = match metric {
+                "auto" => points.mean_auto(),
+                "teleop" => points.mean_tele(),
+                "climb" => points.mean_climb(),
+                _ => points.mean_total(),
+            };
+            (*match_number as f32, value, team.to_string())
+        })
+        .collect();
+
+    let last_match = matches.keys().map(|(_, m)| *m).max().unwrap_or(1).max(1);
+    let max_value = line_data.iter()
+        .map(|(_, v, _)| *v)
+        .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        .unwrap_or(0f32)
+        .max(1f32);
+
+    // Define chart related sizes.
+    let (width, height) = query.size(1300, 500);
+    let (top, right, bottom, left) = if width < 700 { (90, 15, 50, 45) } else { (90, 40, 50, 60) };
+
+    let x = ScaleLinear::new()
+        .set_domain(vec![0_f32, last_match as f32])
+        .set_range(vec![0, width - left - right]);
+
+    let y = ScaleLinear::new()
+        .set_domain(vec![0_f32, max_value])
+        .set_range(vec![height - top - bottom, 0]);
+
+    // keep the team colors the same as on the radar by keying in the order they were asked for
+    let view = LineSeriesView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_marker_type(MarkerType::Circle)
+        .set_label_visibility(false)
+        .set_keys(numbers.iter().map(|t| t.to_string()).collect())
+        .load_data(&line_data).map_err(ErrorInternalServerError)?;
+
+    let chart = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(format!("{} per Match", title))
+        .add_legend_at(charts::AxisPosition::Top)
+        .add_view(view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label(title)
+        .add_bottom_axis_label("Match");
+
+    query.respond(chart).await
+}
+
+/// radar chart of auto, teleop, climb, accuracy and consistency, each scaled so the best team at the event reaches the edge
+pub async fn get_comparison_radar(pool: web::Data<DbPool>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    let numbers = match super::parse_teams(&query.teams, MAX_COMPARED) {
+        Ok(numbers) => numbers,
+        Err(e) => return Ok(bad_query(e)),
+    };
+    let data = compare_by_team(pool).await?;
+
+    let mut best = [0f32; 5];
+    for info in data.values() {
+        for (b, v) in best.iter_mut().zip(info.radar_values()) {
+            *b = b.max(v);
+        }
+    }
+
+    use svg::node::element::{Group, Line, Polygon, Rectangle, Text, Circle};
+    use svg::node::Text as TextNode;
+    use std::f32::consts::PI;
+
+    // drawn at this size and scaled to whatever size was asked for
+    let (view_width, view_height) = (700, 640);
+    let (width, height) = query.size(view_width, view_height);
+    let (center_x, center_y) = (350f32, 350f32);
+    let radius = 220f32;
+    // the first axis points straight up and the rest go clockwise
+    let point = |axis: usize, fraction: f32| {
+        let angle = axis as f32 * 2f32 * PI / RADAR_AXES.len() as f32 - PI / 2f32;
+        (center_x + radius * fraction * angle.cos(), center_y + radius * fraction * angle.sin())
+    };
+    let polygon_points = |fractions: &[f32]| {
+        fractions.iter().enumerate()
+            .map(|(axis, f)| {
+                let (x, y) = point(axis, *f);
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut grid = Group::new().set("class", "grid");
+    for ring in 1..=4 {
+        grid = grid.add(Polygon::new()
+            .set("points", polygon_points(&[ring as f32 / 4f32; 5]))
+            .set("fill", "none")
+            .set("stroke", "#ddd"));
+    }
+    for (axis, label) in RADAR_AXES.iter().enumerate() {
+        let (x, y) = point(axis, 1f32);
+        let (label_x, label_y) = point(axis, 1.15);
+        grid = grid
+            .add(Line::new()
+                .set("x1", center_x).set("y1", center_y)
+                .set("x2", x).set("y2", y)
+                .set("stroke", "#bbb"))
+            .add(Text::new()
+                .set("x", label_x).set("y", label_y)
+                .set("dy", ".35em")
+                .set("text-anchor", "middle")
+                .set("font-family", "sans-serif")
+                .set("font-size", "14px")
+                .set("fill", "#777")
+                .add(TextNode::new(*label)));
+    }
+
+    let colors = charts::Color::color_scheme_10();
+    let mut teams = Group::new().set("class", "teams");
+    let mut legend = Group::new().set("class", "legend").set("transform", "translate(20,60)");
+    for (i, number) in numbers.iter().enumerate() {
+        let color = colors[i % colors.len()].as_hex();
+        legend = legend
+            .add(Rectangle::new()
+                .set("x", 0).set("y", i * 20)
+                .set("width", 12).set("height", 12)
+                .set("fill", color.as_str()))
+            .add(Text::new()
+                .set("x", 18).set("y", i * 20 + 6)
+                .set("dy", ".35em")
+                .set("font-family", "sans-serif")
+                .set("font-size", "12px")
+                .set("fill", "#777")
+                .add(TextNode::new(number.to_string())));
+
+        let info = match data.get(number) {
+            Some(info) => info,
+            None => continue,
+        };
+        let fractions: Vec<f32> = info.radar_values().iter().zip(best)
+            .map(|(v, b)| if b > 0f32 { v / b } else { 0f32 })
+            .collect();
+        teams = teams.add(Polygon::new()
+            .set("points", polygon_points(&fractions))
+            .set("fill", color.as_str())
+            .set("fill-opacity", 0.15)
+            .set("stroke", color.as_str())
+            .set("stroke-width", 2));
+        for (axis, f) in fractions.iter().enumerate() {
+            let (x, y) = point(axis, *f);
+            teams = teams.add(Circle::new()
+                .set("cx", x).set("cy", y).set("r", 3)
+                .set("fill", color.as_str()));
+        }
+    }
+
+    let title = Text::new()
+        .set("x", view_width / 2).set("y", 25)
+        .set("dy", ".35em")
+        .set("text-anchor", "middle")
+        .set("font-family", "sans-serif")
+        .set("font-size", "24px")
+        .set("fill", "#777")
+        .add(TextNode::new("Compared to the Best at the Event"));
+
+    let document = svg::Document::new()
+        .set("width", width)
+        .set("height", height)
+        .set("viewBox", (0i32, 0i32, view_width, view_height))
+        .add(title)
+        .add(grid)
+        .add(teams)
+        .add(legend);
+
+    query.respond_document(document).await
+}
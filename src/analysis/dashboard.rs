@@ -0,0 +1,130 @@
+use std::{collections::{BTreeMap, BTreeSet}, cmp::Ordering};
+use diesel::prelude::*;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use askama::Template;
+use crate::{HttpResult, models::{RobotMatchInfo, ScheduledMatch}, DbPool, DatabaseError};
+use super::{compare::CompareInfo, points::match_points, by_team};
+
+const TOP_TEAMS: usize = 3;
+const RECENT_SUBMISSIONS: usize = 10;
+
+/// How far along some part of the scouting is
+pub struct Progress {
+    label: String,
+    done: usize,
+    total: usize,
+}
+
+impl Progress {
+    fn percent(&self) -> usize {
+        (self.done * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+/// The best few teams by one metric, with the value to show next to each
+pub struct Leaders {
+    metric: &'static str,
+    teams: Vec<(i32, String)>,
+}
+
+pub struct RecentSubmission {
+    team: i32,
+    match_number: i32,
+    scout: String,
+    points: i32,
+}
+
+#[derive(Template)]
+#[template(path = "dashboard.html")]
+pub struct Dashboard {
+    entries: usize,
+    matches_scouted: usize,
+    teams_scouted: usize,
+    scouts: usize,
+    progress: Vec<Progress>,
+    leaders: Vec<Leaders>,
+    recent: Vec<RecentSubmission>,
+}
+
+/// the top teams by a metric, higher being better
+fn leaders(metric: &'static str, teams: &BTreeMap<i32, CompareInfo>, value: impl Fn(&CompareInfo) -> f32, show: impl Fn(f32) -> String) -> Leaders {
+    let mut ranked: Vec<(i32, f32)> = teams.iter().map(|(team, info)| (*team, value(info))).collect();
+    ranked.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    Leaders {
+        metric,
+        teams: ranked.into_iter().take(TOP_TEAMS).map(|(team, v)| (team, show(v))).collect(),
+    }
+}
+
+/// an overview of the event, with how much has been scouted and who's doing best
+pub async fn get_dashboard(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+    let (infos, event_teams, schedule) = web::block(move || -> Result<_, DatabaseError> {
+        let conn = pool.get()?;
+        let infos: Vec<RobotMatchInfo> = {
+            use crate::schema::data::dsl::*;
+            data.order_by(submitted_at.desc()).load(&conn)?
+        };
+        let teams: Vec<i32> = {
+            use crate::schema::event_teams::dsl::*;
+            event_teams.select(team).load(&conn)?
+        };
+        let matches: Vec<ScheduledMatch> = {
+            use crate::schema::schedule::dsl::*;
+            schedule.load(&conn)?
+        };
+        Ok((infos, teams, matches))
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    let scouted: BTreeSet<(i32, i32)> = infos.iter().map(|i| (i.team, i.match_number)).collect();
+    let teams: BTreeMap<i32, CompareInfo> = by_team(infos.iter().cloned());
+
+    let mut progress = Vec::new();
+    if !event_teams.is_empty() {
+        progress.push(Progress {
+            label: "Event teams scouted at least once".to_string(),
+            done: event_teams.iter().filter(|t| teams.contains_key(t)).count(),
+            total: event_teams.len(),
+        });
+    }
+    // only count the matches that should have been played by now
+    let last_match = infos.iter().map(|i| i.match_number).max().unwrap_or(0);
+    let played: Vec<(i32, i32)> = schedule.iter()
+        .filter(|m| m.match_number <= last_match)
+        .flat_map(|m| m.red().into_iter().chain(m.blue()).map(move |t| (t, m.match_number)))
+        .collect();
+    if !played.is_empty() {
+        progress.push(Progress {
+            label: format!("Robots scouted in matches 1 to {}", last_match),
+            done: played.iter().filter(|p| scouted.contains(p)).count(),
+            total: played.len(),
+        });
+    }
+
+    let dashboard = Dashboard {
+        entries: infos.len(),
+        matches_scouted: infos.iter().map(|i| i.match_number).collect::<BTreeSet<i32>>().len(),
+        teams_scouted: teams.len(),
+        scouts: infos.iter().map(|i| i.scout.as_str()).collect::<BTreeSet<&str>>().len(),
+        progress,
+        leaders: vec![
+            leaders("Points", &teams, |i| i.points.mean_total(), |v| format!("{:.1}", v)),
+            leaders("Auto", &teams, |i| i.points.mean_auto(), |v| format!("{:.1}", v)),
+            leaders("Teleop", &teams, |i| i.points.mean_tele(), |v| format!("{:.1}", v)),
+            leaders("Climb", &teams, |i| i.points.mean_climb(), |v| format!("{:.1}", v)),
+            leaders("Accuracy", &teams, |i| i.accuracy.overall().unwrap_or(0f32), |v| format!("{:.0}%", v * 100f32)),
+            leaders("Cargo per Match", &teams, |i| i.accuracy.cargo_per_match(), |v| format!("{:.1}", v)),
+        ],
+        recent: infos.iter().take(RECENT_SUBMISSIONS).map(|i| RecentSubmission {
+            team: i.team,
+            match_number: i.match_number,
+            scout: i.scout.clone(),
+            points: match_points(i),
+        }).collect(),
+    };
+
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(dashboard.render().map_err(ErrorInternalServerError)?)
+    )
+}
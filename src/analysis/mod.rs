@@ -1,9 +1,187 @@
+mod options;
 mod raw_data;
 mod points;
 mod accuracy;
 mod climb;
+mod quality;
+mod team;
+mod ratings;
+mod shots;
+mod timing;
+mod bonus;
+mod compare;
+mod strategy;
+mod dashboard;
+mod card;
 
 pub use raw_data::get_data_listing;
-pub use points::get_points_chart;
-pub use accuracy::get_accuracy_chart;
-pub use climb::get_climb_chart;
+pub use points::{get_points_chart, get_points_box_chart, get_points_violin_chart, get_points_histogram, get_points_grid, match_points};
+pub use accuracy::{get_accuracy_chart, get_accuracy_table, get_accuracy_breakdown_chart};
+pub use climb::{get_climb_chart, get_team_climb_donut};
+pub use quality::get_quality_report;
+pub use team::get_team_page;
+pub use ratings::get_ratings_table;
+pub use shots::get_shot_heatmap;
+pub use timing::get_timing_table;
+pub use bonus::get_match_plan;
+pub use compare::{get_comparison, get_comparison_matches_chart, get_comparison_radar};
+pub use strategy::{get_strategy_sheet, get_sparkline};
+pub use dashboard::get_dashboard;
+pub use card::get_team_card;
+
+use std::{ops::AddAssign, collections::BTreeMap};
+use crate::models::RobotMatchInfo;
+
+/// Totals over some entries that can be divided back down,
+/// so a match counts once no matter how many scouts watched it
+trait MatchAverage: From<RobotMatchInfo> + AddAssign + Default {
+    /// turn the totals of one match's entries into their average
+    fn averaged(self, entries: u32) -> Self;
+}
+
+/// add up the entries of each (team, match) and average them
+fn by_match<T: MatchAverage>(infos: impl IntoIterator<Item = RobotMatchInfo>) -> BTreeMap<(i32, i32), T> {
+    let mut matches: BTreeMap<(i32, i32), (T, u32)> = BTreeMap::new();
+    for info in infos {
+        let (total, entries) = matches.entry((info.team, info.match_number)).or_default();
+        *total += info.into();
+        *entries += 1;
+    }
+    matches.into_iter()
+        .map(|(key, (total, entries))| (key, total.averaged(entries)))
+        .collect()
+}
+
+/// add up each team's matches, averaging the ones scouted more than once first
+fn by_team<T: MatchAverage>(infos: impl IntoIterator<Item = RobotMatchInfo>) -> BTreeMap<i32, T> {
+    let mut teams: BTreeMap<i32, T> = BTreeMap::new();
+    for ((team, _), info) in by_match::<T>(infos) {
+        *teams.entry(team).or_default() += info;
+    }
+    teams
+}
+
+/// a count for a table cell, which is only fractional when scouts disagreed about a match
+fn show_count(count: f32) -> String {
+    if (count - count.round()).abs() < 0.05 {
+        format!("{:.0}", count)
+    } else {
+        format!("{:.1}", count)
+    }
+}
+
+/// read up to `max` team numbers separated by commas or spaces, leaving out repeats
+fn parse_teams(input: &str, max: usize) -> Result<Vec<i32>, String> {
+    let mut numbers = Vec::new();
+    for word in input.split(|c: char| c.is_whitespace() || c == ',').filter(|w| !w.is_empty()) {
+        match word.parse::<i32>() {
+            Ok(n) if n > 0 => if !numbers.contains(&n) {
+                numbers.push(n);
+            },
+            _ => return Err(format!("\"{}\" isn't a team number", word)),
+        }
+    }
+    if numbers.len() > max {
+        return Err(format!("Only {} teams at a time", max));
+    }
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{Alliance, LeftTarmac, Climb};
+    use super::{points::TeamPointsInfo, climb::ClimbInfo, ratings::RatingsInfo};
+
+    pub(super) fn entry(team: i32, match_number: i32, scout: &str, teleop_high_made: i32, climb: Climb, speed: Option<i32>) -> RobotMatchInfo {
+        RobotMatchInfo {
+            team,
+            match_number,
+            alliance: Alliance::Red,
+            left_tarmac: LeftTarmac::No,
+            auto_high_made: 0,
+            auto_high_missed: 0,
+            auto_low_made: 0,
+            auto_low_missed: 0,
+            teleop_high_made,
+            teleop_high_missed: 0,
+            teleop_low_made: 0,
+            teleop_low_missed: 0,
+            climb,
+            notes: String::new(),
+            scout: scout.to_string(),
+            submitted_at: 0,
+            driver_skill: None,
+            defense_played: None,
+            defense_resilience: None,
+            speed,
+            penalties_drawn: None,
+        }
+    }
+
+    #[test]
+    fn nothing_scouted_means_no_teams() {
+        assert!(by_team::<TeamPointsInfo>(Vec::new()).is_empty());
+        assert!(by_match::<TeamPointsInfo>(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn a_co_scouted_match_counts_once() {
+        let teams: BTreeMap<i32, TeamPointsInfo> = by_team(vec![
+            entry(33, 1, "Sam", 10, Climb::No, None),
+            entry(33, 1, "Alex", 6, Climb::No, None),
+            entry(33, 2, "Sam", 1, Climb::No, None),
+        ]);
+        let points = &teams[&33];
+        assert_eq!(points.num_matches, 2);
+        // match 1 averages to 16 points, match 2 is 2 points
+        assert_eq!(points.mean_total(), 9f32);
+    }
+
+    #[test]
+    fn disagreeing_scouts_split_the_climb() {
+        let climbs: ClimbInfo = by_team(vec![
+            entry(33, 1, "Sam", 0, Climb::High, None),
+            entry(33, 1, "Alex", 0, Climb::Mid, None),
+        ]).remove(&33).unwrap();
+        assert_eq!((climbs.high_climbs, climbs.mid_climbs, climbs.total()), (0.5, 0.5, 1f32));
+    }
+
+    #[test]
+    fn ratings_average_per_match_first() {
+        let ratings: RatingsInfo = by_team(vec![
+            entry(33, 1, "Sam", 0, Climb::No, Some(5)),
+            entry(33, 1, "Alex", 0, Climb::No, Some(5)),
+            entry(33, 1, "Kim", 0, Climb::No, None),
+            entry(33, 2, "Sam", 0, Climb::No, Some(2)),
+        ]).remove(&33).unwrap();
+        assert_eq!(ratings.get("speed").mean(), Some(3.5));
+        assert_eq!(ratings.get("driver_skill").mean(), None);
+    }
+
+    #[test]
+    fn teams_stay_apart() {
+        let teams: BTreeMap<i32, TeamPointsInfo> = by_team(vec![
+            entry(33, 1, "Sam", 1, Climb::No, None),
+            entry(67, 1, "Sam", 2, Climb::No, None),
+        ]);
+        assert_eq!(teams.keys().copied().collect::<Vec<i32>>(), vec![33, 67]);
+        assert_eq!(teams[&67].mean_total(), 4f32);
+    }
+
+    #[test]
+    fn counts_only_show_decimals_when_needed() {
+        assert_eq!(show_count(0f32), "0");
+        assert_eq!(show_count(3.0000002), "3");
+        assert_eq!(show_count(2.5), "2.5");
+    }
+
+    #[test]
+    fn reads_team_lists() {
+        assert_eq!(parse_teams("33, 67 33  254", 6), Ok(vec![33, 67, 254]));
+        assert_eq!(parse_teams("", 6), Ok(vec![]));
+        assert!(parse_teams("33 abc", 6).is_err());
+        assert!(parse_teams("0", 6).is_err());
+        assert!(parse_teams("1 2 3 4", 3).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use std::cmp::Ordering;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use charts::{BarMode, Binning, Chart, ChartError, HistogramMode, Page};
+use serde::Deserialize;
+use crate::HttpResult;
+
+const MIN_SIZE: isize = 200;
+const MAX_SIZE: isize = 4000;
+// narrower than this and the side margins shrink to leave room for the bars
+const NARROW_WIDTH: isize = 700;
+// tick labels are 12px, so a character is about 7px wide
+const TICK_CHAR_WIDTH: isize = 7;
+
+/// Query parameters every chart takes for its size and which teams it shows
+#[derive(Deserialize)]
+pub struct ChartQuery {
+    width: Option<isize>,
+    height: Option<isize>,
+    // asc, desc or team, anything else is asc
+    #[serde(default)]
+    sort: String,
+    // only these teams, separated by commas
+    #[serde(default)]
+    pub(super) teams: String,
+    // only the best N teams by the metric
+    top: Option<usize>,
+    // which value to sort and pick the top teams by, each chart has its own
+    #[serde(default)]
+    pub(super) metric: String,
+    // svg, or png for places that can't show svg like Discord
+    #[serde(default)]
+    format: String,
+    // stacked, grouped to put each part of a bar next to the others,
+    // or normalized to stack the share of each part. histograms take
+    // stacked or overlaid
+    #[serde(default)]
+    bars: String,
+    // how many bins a histogram has, picked from the data when left out
+    bins: Option<usize>,
+}
+
+impl ChartQuery {
+    /// the size asked for, kept to something that can be drawn
+    pub(super) fn size(&self, default_width: isize, default_height: isize) -> (isize, isize) {
+        (chart_size(self.width, default_width), chart_size(self.height, default_height))
+    }
+
+    /// the teams to chart, in the order they should be charted
+    pub(super) fn select<T>(&self, data: impl IntoIterator<Item = (i32, T)>, value: impl Fn(&T) -> f32) -> Result<Vec<(i32, T)>, String> {
+        let wanted = super::parse_teams(&self.teams, usize::MAX)?;
+        let mut data: Vec<(i32, T)> = data.into_iter()
+            .filter(|(team, _)| wanted.is_empty() || wanted.contains(team))
+            .collect();
+
+        let by_value = |(_, a): &(i32, T), (_, b): &(i32, T)| value(a).partial_cmp(&value(b)).unwrap_or(Ordering::Equal);
+        if let Some(top) = self.top {
+            data.sort_by(|a, b| by_value(b, a));
+            data.truncate(top);
+        }
+        match self.sort.as_str() {
+            "desc" => data.sort_by(|a, b| by_value(b, a)),
+            "team" => data.sort_by_key(|(team, _)| *team),
+            _ => data.sort_by(by_value),
+        }
+        Ok(data)
+    }
+
+    /// how the bar charts should lay out the parts of each bar
+    pub(super) fn bar_mode(&self) -> BarMode {
+        match self.bars.as_str() {
+            "grouped" => BarMode::Grouped,
+            "normalized" => BarMode::Normalized,
+            _ => BarMode::Stacked,
+        }
+    }
+
+    /// whether the histograms of several teams are stacked or drawn over each other
+    pub(super) fn histogram_mode(&self) -> HistogramMode {
+        match self.bars.as_str() {
+            "stacked" => HistogramMode::Stacked,
+            _ => HistogramMode::Overlaid,
+        }
+    }
+
+    /// how a histogram splits its values into bins
+    pub(super) fn binning(&self) -> Binning {
+        match self.bins {
+            Some(bins) => Binning::Count(bins.clamp(1, 100)),
+            None => Binning::FreedmanDiaconis,
+        }
+    }
+
+    /// draw the chart and send it back in the format that was asked for
+    pub(super) async fn respond(&self, chart: Chart) -> HttpResult<HttpResponse> {
+        self.render(move || chart.to_document()).await
+    }
+
+    /// draw a page of charts and send it back in the format that was asked for
+    pub(super) async fn respond_page(&self, page: Page) -> HttpResult<HttpResponse> {
+        self.render(move || page.to_document()).await
+    }
+
+    /// send back a chart that was drawn by hand, in the format that was asked for
+    pub(super) async fn respond_document(&self, document: svg::Document) -> HttpResult<HttpResponse> {
+        self.render(move || Ok(document)).await
+    }
+
+    async fn render(&self, draw: impl FnOnce() -> Result<svg::Document, ChartError> + Send + 'static) -> HttpResult<HttpResponse> {
+        let png = match self.format.as_str() {
+            "" | "svg" => false,
+            "png" => true,
+            other => return Ok(bad_query(format!("\"{}\" isn't a chart format, use svg or png", other))),
+        };
+        // rasterizing takes long enough that it shouldn't hold up the other requests
+        let image = web::block(move || -> Result<Vec<u8>, ChartError> {
+            let svg = draw()?.to_string();
+            if png {
+                charts::svg_to_png(&svg)
+            } else {
+                Ok(svg.into_bytes())
+            }
+        }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+        Ok(HttpResponse::Ok()
+            .content_type(if png { "image/png" } else { "image/svg+xml; charset=utf-8" })
+            .body(image))
+    }
+}
+
+/// a width or height from a query, kept to something that can be drawn
+fn chart_size(asked: Option<isize>, default: isize) -> isize {
+    asked.unwrap_or(default).clamp(MIN_SIZE, MAX_SIZE)
+}
+
+/// Size and margins for a chart with a band of labels along the bottom
+pub(super) struct ChartLayout {
+    pub(super) width: isize,
+    pub(super) height: isize,
+    pub(super) top: isize,
+    pub(super) right: isize,
+    pub(super) bottom: isize,
+    pub(super) left: isize,
+    // degrees to turn the bottom tick labels so they don't run into each other
+    pub(super) rotation: isize,
+}
+
+impl ChartLayout {
+    pub(super) fn for_bands(width: isize, height: isize, labels: &[String]) -> Self {
+        let (left, right) = if width < NARROW_WIDTH { (45, 15) } else { (60, 40) };
+        let top = 90;
+
+        let band = (width - left - right) / (labels.len().max(1) as isize);
+        let longest = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0) as isize * TICK_CHAR_WIDTH;
+        let rotation = if longest + 4 <= band {
+            0
+        } else if band >= 10 {
+            -45
+        } else {
+            -90
+        };
+        // the rotated labels hang below the axis by about this much
+        let overhang = (longest as f32 * (rotation as f32).to_radians().sin().abs()) as isize;
+        // always leave some of the height for the bars themselves
+        let bottom = (50 + overhang).min(height - top - MIN_SIZE / 2).max(50);
+
+        ChartLayout { width, height, top, right, bottom, left, rotation }
+    }
+
+    pub(super) fn inner_width(&self) -> isize {
+        self.width - self.left - self.right
+    }
+
+    pub(super) fn inner_height(&self) -> isize {
+        self.height - self.top - self.bottom
+    }
+}
+
+/// a chart query that can't be drawn, like a team list with a typo
+pub(super) fn bad_query(error: String) -> HttpResponse {
+    HttpResponse::BadRequest()
+        .content_type("text/plain; charset=utf-8")
+        .body(error)
+}
@@ -2,13 +2,15 @@ use std::{ops::{Add, AddAssign}, collections::BTreeMap};
 use diesel::prelude::*;
 use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
 use crate::{HttpResult, models::RobotMatchInfo, DbPool, DatabaseError};
+use super::options::{ChartQuery, ChartLayout, bad_query};
+use super::{MatchAverage, by_match, by_team};
 
 #[derive(Clone, Default)]
-struct TeamPointsInfo {
-    num_matches: u32,
-    auto_points: i32,
-    tele_points: i32,
-    climb_points: i32,
+pub(super) struct TeamPointsInfo {
+    pub(super) num_matches: u32,
+    auto_points: f32,
+    tele_points: f32,
+    climb_points: f32,
 }
 
 impl From<RobotMatchInfo> for TeamPointsInfo {
@@ -29,9 +31,9 @@ impl From<RobotMatchInfo> for TeamPointsInfo {
 
         TeamPointsInfo {
             num_matches: 1,
-            auto_points,
-            tele_points,
-            climb_points,
+            auto_points: auto_points as f32,
+            tele_points: tele_points as f32,
+            climb_points: climb_points as f32,
         }
     }
 }
@@ -54,29 +56,55 @@ impl AddAssign for TeamPointsInfo {
     }
 }
 
+impl MatchAverage for TeamPointsInfo {
+    fn averaged(self, entries: u32) -> Self {
+        TeamPointsInfo {
+            num_matches: self.num_matches / entries,
+            auto_points: self.auto_points / entries as f32,
+            tele_points: self.tele_points / entries as f32,
+            climb_points: self.climb_points / entries as f32,
+        }
+    }
+}
+
 impl TeamPointsInfo {
-    fn total(&self) -> i32 {
+    fn total(&self) -> f32 {
         self.auto_points + self.tele_points + self.climb_points
     }
 
-    fn mean_total(&self) -> f32 {
-        (self.total() as f32) / (self.num_matches as f32)
+    pub(super) fn mean_total(&self) -> f32 {
+        self.total() / (self.num_matches as f32)
     }
 
-    fn mean_auto(&self) -> f32 {
-        (self.auto_points as f32) / (self.num_matches as f32)
+    pub(super) fn mean_auto(&self) -> f32 {
+        self.auto_points / (self.num_matches as f32)
     }
 
-    fn mean_tele(&self) -> f32 {
-        (self.auto_points as f32) / (self.num_matches as f32)
+    pub(super) fn mean_tele(&self) -> f32 {
+        self.tele_points / (self.num_matches as f32)
     }
 
-    fn mean_climb(&self) -> f32 {
-        (self.climb_points as f32) / (self.num_matches as f32)
+    pub(super) fn mean_climb(&self) -> f32 {
+        self.climb_points / (self.num_matches as f32)
     }
 }
 
-pub async fn get_points_chart(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+/// total points a robot scored in a single match
+pub fn match_points(info: &RobotMatchInfo) -> i32 {
+    TeamPointsInfo::from(info.clone()).total() as i32
+}
+
+/// pick the value a points chart is sorted by from the metric in the query
+fn points_metric(metric: &str) -> fn(&TeamPointsInfo) -> f32 {
+    match metric {
+        "auto" => TeamPointsInfo::mean_auto,
+        "teleop" => TeamPointsInfo::mean_tele,
+        "climb" => TeamPointsInfo::mean_climb,
+        _ => TeamPointsInfo::mean_total,
+    }
+}
+
+pub async fn get_points_chart(pool: web::Data<DbPool>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
     // could write more of this as an sql query
     let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
         use crate::schema::data::dsl::*;
@@ -85,51 +113,51 @@ pub async fn get_points_chart(pool: web::Data<DbPool>) -> HttpResult<HttpRespons
     }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
 
 
-    use charts::{Chart, VerticalBarView, ScaleBand, ScaleLinear, BarLabelPosition};
+    use charts::{Chart, VerticalBarView, ScaleBand, ScaleLinear, BarLabelPosition, BarMode};
     use std::cmp::Ordering;
 
-    let mut data: BTreeMap<i32, TeamPointsInfo> = BTreeMap::new();
-
-    for info in infos {
-        *data.entry(info.team)
-            .or_insert(Default::default()) += info.into();
-    }
+    let data: BTreeMap<i32, TeamPointsInfo> = by_team(infos);
 
+    let data = match query.select(data, points_metric(&query.metric)) {
+        Ok(data) => data,
+        Err(e) => return Ok(bad_query(e)),
+    };
 
-    let max_points = data.values()
-        .map(|i| i.mean_total())
+    // grouped bars only get as tall as the biggest single part
+    let bar_mode = query.bar_mode();
+    let max_points = data.iter()
+        .map(|(_, i)| match bar_mode {
+            BarMode::Grouped => i.mean_auto().max(i.mean_tele()).max(i.mean_climb()),
+            BarMode::Normalized => 1f32,
+            BarMode::Stacked => i.mean_total(),
+        })
         .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
         .unwrap_or(200f32);
 
-    // Define chart related sizes.
-    let width = 1300;
-    let height = 600;
-    let (top, right, bottom, left) = (90, 40, 50, 60);
+    // Define chart related sizes, leaving room for the team numbers to fit.
+    let (width, height) = query.size(1300, 600);
+    let labels: Vec<String> = data.iter().map(|(num, _)| format!("{}", num)).collect();
+    let layout = ChartLayout::for_bands(width, height, &labels);
 
     // Create a linear scale that will interpolate values in [0, 100] range to corresponding
     // values in [0, availableWidth] range (the width of the chart without the margins).
     let y = ScaleLinear::new()
         .set_domain(vec![0_f32, max_points])
-        .set_range(vec![height - top - bottom, 0]);
+        .set_range(vec![layout.inner_height(), 0]);
 
-    // have to convert to a vec to sort by points
-    let mut data: Vec<(i32, TeamPointsInfo)> = data.into_iter().collect();
-    data.sort_by(|(_, a), (_, b)| a.mean_total().partial_cmp(&b.mean_total()).unwrap_or(Ordering::Equal));
-
-    // Create a band scale that maps team numbers to values in the [0, availableHeight]
-    // range (the height of the chart without the margins).
+    // Create a band scale that maps team numbers to values in the [0, availableWidth]
+    // range (the width of the chart without the margins).
     let x = ScaleBand::new()
-        .set_domain(
-            data.iter().map(|(num, _)| format!("{}", num)).collect())
-        .set_range(vec![0, height - top - bottom]);
+        .set_domain(labels)
+        .set_range(vec![0, layout.inner_width()]);
 
 
     let bar_data: Vec<(String, f32, String)> = data.into_iter()
         .flat_map(|(team, info)| {
             [
-                (format!("{}", team), (info.auto_points as f32) / (info.num_matches as f32), "Auto".to_string()),
-                (format!("{}", team), (info.tele_points as f32) / (info.num_matches as f32), "TeleOp".to_string()),
-                (format!("{}", team), (info.climb_points as f32) / (info.num_matches as f32), "Climb".to_string())
+                (format!("{}", team), info.mean_auto(), "Auto".to_string()),
+                (format!("{}", team), info.mean_tele(), "TeleOp".to_string()),
+                (format!("{}", team), info.mean_climb(), "Climb".to_string())
             ]
         })
         .collect();
@@ -138,29 +166,245 @@ pub async fn get_points_chart(pool: web::Data<DbPool>) -> HttpResult<HttpRespons
     let view = VerticalBarView::new()
         .set_x_scale(&x)
         .set_y_scale(&y)
+        .set_bar_mode(bar_mode)
         .set_label_position(BarLabelPosition::Center)
-        .load_data(&bar_data).unwrap();
+        .set_label_visibility(bar_mode == BarMode::Stacked)
+        .load_data(&bar_data).map_err(ErrorInternalServerError)?;
 
     // Generate and save the chart.
-    let svg_content = Chart::new()
+    let chart = Chart::new()
         .set_width(width)
         .set_height(height)
-        .set_margins(top, right, bottom, left)
+        .set_margins(layout.top, layout.right, layout.bottom, layout.left)
         .add_title(String::from("Average Points"))
         .add_legend_at(charts::AxisPosition::Top)
-        .add_view(&view)
+        .add_view(view)
         .add_axis_bottom(&x)
         .add_axis_left(&y)
+        .set_bottom_axis_tick_label_rotation(layout.rotation)
         .add_left_axis_label("Points")
-        .add_bottom_axis_label("Team")
-        .to_svg().unwrap();
+        .add_bottom_axis_label("Team");
+
+    query.respond(chart).await
+}
+
+/// how each team's points are spread over its matches, as boxes
+pub async fn get_points_box_chart(pool: web::Data<DbPool>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    points_distribution_chart(pool, query, false).await
+}
+
+/// how each team's points are spread over its matches, as violins
+pub async fn get_points_violin_chart(pool: web::Data<DbPool>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    points_distribution_chart(pool, query, true).await
+}
+
+async fn points_distribution_chart(pool: web::Data<DbPool>, query: web::Query<ChartQuery>, violin: bool) -> HttpResult<HttpResponse> {
+    let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.order_by(team.asc()).load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    use charts::{Chart, BoxPlotView, ViolinView, ScaleBand, ScaleLinear};
+
+    // every scouted match is a sample, even when a match was scouted twice
+    let mut samples: BTreeMap<i32, Vec<f32>> = BTreeMap::new();
+    for info in infos.iter() {
+        samples.entry(info.team).or_default().push(match_points(info) as f32);
+    }
+
+    // teams are sorted by their median match
+    let median = |points: &Vec<f32>| {
+        let mut points = points.clone();
+        points.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        points.get(points.len() / 2).copied().unwrap_or(0f32)
+    };
+    let data = match query.select(samples, median) {
+        Ok(data) => data,
+        Err(e) => return Ok(bad_query(e)),
+    };
+
+    let max_points = data.iter()
+        .flat_map(|(_, points)| points.iter().copied())
+        .fold(10f32, f32::max);
+
+    let (width, height) = query.size(1300, 600);
+    let labels: Vec<String> = data.iter().map(|(num, _)| num.to_string()).collect();
+    let layout = ChartLayout::for_bands(width, height, &labels);
+
+    let y = ScaleLinear::new()
+        .set_domain(vec![0_f32, max_points])
+        .set_range(vec![layout.inner_height(), 0]);
+    let x = ScaleBand::new()
+        .set_domain(labels)
+        .set_range(vec![0, layout.inner_width()]);
+
+    let sample_data: Vec<(String, f32)> = data.into_iter()
+        .flat_map(|(team, points)| points.into_iter().map(move |p| (team.to_string(), p)))
+        .collect();
+
+    let chart = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(layout.top, layout.right, layout.bottom, layout.left)
+        .add_title(String::from("Points per Match"))
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .set_bottom_axis_tick_label_rotation(layout.rotation)
+        .add_left_axis_label("Points")
+        .add_bottom_axis_label("Team");
+
+    let chart = if violin {
+        chart.add_view(ViolinView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&sample_data).map_err(ErrorInternalServerError)?)
+    } else {
+        chart.add_view(BoxPlotView::new()
+            .set_x_scale(&x)
+            .set_y_scale(&y)
+            .load_data(&sample_data).map_err(ErrorInternalServerError)?)
+    };
+
+    query.respond(chart).await
+}
+
+/// how many matches ended up with each total, for the whole event or
+/// with a histogram per team for the teams in the query
+pub async fn get_points_histogram(pool: web::Data<DbPool>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    let teams = match super::parse_teams(&query.teams, usize::MAX) {
+        Ok(teams) => teams,
+        Err(e) => return Ok(bad_query(e)),
+    };
 
-    let document = svg::Document::new()
-        .set("width", width)
-        .set("height", height)
-        .set("viewBox", (0i32, 0i32, width, height))
-        .add(svg_content);
+    let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.order_by(team.asc()).load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    use charts::{Chart, HistogramView, ScaleLinear};
+
+    // without teams every match goes into one histogram under an empty key
+    let values: Vec<(f32, String)> = infos.iter()
+        .filter(|info| teams.is_empty() || teams.contains(&info.team))
+        .map(|info| (match_points(info) as f32, if teams.is_empty() { String::new() } else { info.team.to_string() }))
+        .collect();
+    let keys = if teams.is_empty() {
+        vec![String::new()]
+    } else {
+        teams.iter().map(|team| team.to_string()).collect()
+    };
+
+    let view = HistogramView::new()
+        .set_keys(keys)
+        .set_binning(query.binning())
+        .set_mode(query.histogram_mode());
+    let (x_domain, y_domain) = view.get_domains(&values);
+
+    let (width, height) = query.size(1000, 500);
+    let (top, right, bottom, left) = (90, 40, 50, 60);
+    let x = ScaleLinear::new()
+        .set_domain(x_domain)
+        .set_range(vec![0, width - left - right]);
+    let y = ScaleLinear::new()
+        .set_domain(y_domain)
+        .set_range(vec![height - top - bottom, 0]);
+
+    let view = view
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .load_data(&values).map_err(ErrorInternalServerError)?;
+
+    let title = match teams.as_slice() {
+        [] => String::from("Points per Match"),
+        [team] => format!("Points per Match for Team {}", team),
+        _ => String::from("Points per Match by Team"),
+    };
+    let mut chart = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(title)
+        .add_view(view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label("Matches")
+        .add_bottom_axis_label("Points");
+    if teams.len() > 1 {
+        chart = chart.add_legend_at(charts::AxisPosition::Top);
+    }
+
+    query.respond(chart).await
+}
+
+/// points each team scored in each of its matches, as a grid of teams by matches
+pub async fn get_points_grid(pool: web::Data<DbPool>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.order_by(team.asc()).load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    use charts::{Chart, HeatmapView, ScaleBand, ColorScale};
+
+    // matches scouted more than once get averaged
+    let mut data: BTreeMap<i32, BTreeMap<i32, TeamPointsInfo>> = BTreeMap::new();
+    for ((team, match_number), points) in by_match(infos) {
+        data.entry(team).or_default().insert(match_number, points);
+    }
+
+    let mean = |matches: &BTreeMap<i32, TeamPointsInfo>| {
+        matches.values().map(TeamPointsInfo::mean_total).sum::<f32>() / matches.len().max(1) as f32
+    };
+    let data = match query.select(data, mean) {
+        Ok(data) => data,
+        Err(e) => return Ok(bad_query(e)),
+    };
+
+    let mut match_numbers: Vec<i32> = data.iter().flat_map(|(_, matches)| matches.keys().copied()).collect();
+    match_numbers.sort_unstable();
+    match_numbers.dedup();
+
+    let (width, height) = query.size(1300, 120 + 30 * data.len().max(1) as isize);
+    let (top, right, bottom, left) = (90, 40, 50, 70);
+
+    let x = ScaleBand::new()
+        .set_domain(match_numbers.iter().map(|m| m.to_string()).collect())
+        .set_range(vec![0, width - left - right])
+        .set_inner_padding(0.05)
+        .set_outer_padding(0f32);
+    let y = ScaleBand::new()
+        .set_domain(data.iter().map(|(team, _)| team.to_string()).collect())
+        .set_range(vec![0, height - top - bottom])
+        .set_inner_padding(0.05)
+        .set_outer_padding(0f32);
+
+    let cells: Vec<(String, String, f32)> = data.iter()
+        .flat_map(|(team, matches)| matches.iter()
+            .map(move |(m, info)| (m.to_string(), team.to_string(), info.mean_total())))
+        .collect();
+
+    let view = HeatmapView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_color_scale(ColorScale::blues())
+        .set_label_visibility(true)
+        .set_label_rounding_precision(0)
+        .set_custom_data_label(String::from("points"))
+        .load_data(&cells).map_err(ErrorInternalServerError)?;
+
+    let chart = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(String::from("Points per Match"))
+        .add_legend_at(charts::AxisPosition::Top)
+        .add_view(view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label("Team")
+        .add_bottom_axis_label("Match");
 
-    Ok(HttpResponse::Ok().content_type("image/svg+xml; charset=utf-8")
-            .body(document.to_string()))
+    query.respond(chart).await
 }
@@ -0,0 +1,337 @@
+use std::collections::BTreeMap;
+use diesel::prelude::*;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use askama::Template;
+use crate::{HttpResult, models::RobotMatchInfo, DbPool, DatabaseError};
+use super::points::match_points;
+
+// counts within this of each other are treated as the same, since it's easy to be off by one
+const COUNT_TOLERANCE: i32 = 1;
+// how many standard deviations from a team's mean before an entry looks suspicious
+const OUTLIER_DEVIATIONS: f32 = 2.0;
+// a team needs this many entries before its mean and deviation mean anything
+const MIN_MATCHES_FOR_OUTLIERS: usize = 4;
+
+/// one field of a match as recorded by each scout that covered it
+pub struct FieldComparison {
+    name: &'static str,
+    values: Vec<String>,
+    agrees: bool,
+}
+
+/// a robot in a match that more than one scout recorded
+pub struct CoScoutedMatch {
+    team: i32,
+    match_number: i32,
+    scouts: Vec<String>,
+    fields: Vec<FieldComparison>,
+}
+
+impl CoScoutedMatch {
+    fn disagreements(&self) -> usize {
+        self.fields.iter().filter(|f| !f.agrees).count()
+    }
+}
+
+/// an entry that probably needs a second look
+pub struct FlaggedEntry {
+    team: i32,
+    match_number: i32,
+    scout: String,
+    reason: String,
+}
+
+#[derive(Default)]
+pub struct ScoutAgreement {
+    scout: String,
+    entries: u32,
+    compared: u32,
+    agreed: u32,
+}
+
+impl ScoutAgreement {
+    fn rate(&self) -> f32 {
+        if self.compared == 0 {
+            0f32
+        } else {
+            (self.agreed as f32) / (self.compared as f32)
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "quality-report.html")]
+pub struct QualityReport {
+    scouts: Vec<ScoutAgreement>,
+    flagged: Vec<FlaggedEntry>,
+    matches: Vec<CoScoutedMatch>,
+}
+
+enum FieldValue {
+    Count(i32),
+    Choice(String),
+}
+
+impl FieldValue {
+    fn agrees_with(&self, other: &FieldValue) -> bool {
+        match (self, other) {
+            (FieldValue::Count(a), FieldValue::Count(b)) => (a - b).abs() <= COUNT_TOLERANCE,
+            (FieldValue::Choice(a), FieldValue::Choice(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldValue::Count(n) => write!(f, "{}", n),
+            FieldValue::Choice(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// the fields that scouts watching the same robot should agree on
+fn compared_fields(i: &RobotMatchInfo) -> Vec<(&'static str, FieldValue)> {
+    vec![
+        ("Left Tarmac", FieldValue::Choice(i.left_tarmac.to_string())),
+        ("Auto High Made", FieldValue::Count(i.auto_high_made)),
+        ("Auto High Missed", FieldValue::Count(i.auto_high_missed)),
+        ("Auto Low Made", FieldValue::Count(i.auto_low_made)),
+        ("Auto Low Missed", FieldValue::Count(i.auto_low_missed)),
+        ("Teleop High Made", FieldValue::Count(i.teleop_high_made)),
+        ("Teleop High Missed", FieldValue::Count(i.teleop_high_missed)),
+        ("Teleop Low Made", FieldValue::Count(i.teleop_low_made)),
+        ("Teleop Low Missed", FieldValue::Count(i.teleop_low_missed)),
+        ("Climb", FieldValue::Choice(i.climb.to_string())),
+    ]
+}
+
+fn mean_and_deviation(values: &[f32]) -> (f32, f32) {
+    let mean = values.iter().sum::<f32>() / (values.len() as f32);
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / (values.len() as f32);
+    (mean, variance.sqrt())
+}
+
+fn build_report(infos: Vec<RobotMatchInfo>) -> QualityReport {
+    let mut scouts: BTreeMap<String, ScoutAgreement> = BTreeMap::new();
+    let mut flagged = Vec::new();
+    let mut matches = Vec::new();
+
+    // entries from different scouts for the same robot and match
+    let mut by_match: BTreeMap<(i32, i32), Vec<&RobotMatchInfo>> = BTreeMap::new();
+
+    for info in infos.iter() {
+        by_match.entry((info.team, info.match_number)).or_default().push(info);
+        let scout = scouts.entry(info.scout.clone()).or_default();
+        scout.scout = info.scout.clone();
+        scout.entries += 1;
+    }
+
+    for ((team, match_number), entries) in by_match.iter() {
+        if entries.len() < 2 {
+            continue;
+        }
+
+        let values: Vec<Vec<(&'static str, FieldValue)>> = entries.iter().map(|e| compared_fields(e)).collect();
+        let mut fields = Vec::new();
+        for field in 0..values[0].len() {
+            let agrees = values.iter().all(|v| v[field].1.agrees_with(&values[0][field].1));
+            fields.push(FieldComparison {
+                name: values[0][field].0,
+                values: values.iter().map(|v| v[field].1.to_string()).collect(),
+                agrees,
+            });
+        }
+
+        // each scout gets credit for every field where they agree with all of their co-scouts
+        for (i, entry) in entries.iter().enumerate() {
+            let scout = scouts.get_mut(&entry.scout).unwrap();
+            for (field, (_, value)) in values[i].iter().enumerate() {
+                let agrees = values.iter().enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .all(|(_, other)| value.agrees_with(&other[field].1));
+                scout.compared += 1;
+                scout.agreed += agrees as u32;
+            }
+        }
+
+        let comparison = CoScoutedMatch {
+            team: *team,
+            match_number: *match_number,
+            scouts: entries.iter().map(|e| e.scout.clone()).collect(),
+            fields,
+        };
+
+        if comparison.disagreements() > 0 {
+            let disagreeing: Vec<&str> = comparison.fields.iter().filter(|f| !f.agrees).map(|f| f.name).collect();
+            for entry in entries.iter() {
+                flagged.push(FlaggedEntry {
+                    team: *team,
+                    match_number: *match_number,
+                    scout: entry.scout.clone(),
+                    reason: format!("disagrees with co-scout on {}", disagreeing.join(", ")),
+                });
+            }
+        }
+
+        matches.push(comparison);
+    }
+
+    // each team's points in every match, with co-scouted matches averaged so they only count once
+    let mut team_points: BTreeMap<i32, Vec<f32>> = BTreeMap::new();
+    for ((team, _), entries) in by_match.iter() {
+        let points = entries.iter().map(|e| match_points(e) as f32).sum::<f32>() / (entries.len() as f32);
+        team_points.entry(*team).or_default().push(points);
+    }
+
+    // then every entry is checked against its team's usual points
+    for (team, points) in team_points.iter() {
+        if points.len() < MIN_MATCHES_FOR_OUTLIERS {
+            continue;
+        }
+        let (mean, deviation) = mean_and_deviation(points);
+        if deviation == 0f32 {
+            continue;
+        }
+        for entry in infos.iter().filter(|i| i.team == *team) {
+            let p = match_points(entry) as f32;
+            if (p - mean).abs() > OUTLIER_DEVIATIONS * deviation {
+                flagged.push(FlaggedEntry {
+                    team: *team,
+                    match_number: entry.match_number,
+                    scout: entry.scout.clone(),
+                    reason: format!("{} points is far from the team's usual {:.1} (± {:.1})", p, mean, deviation),
+                });
+            }
+        }
+    }
+
+    flagged.sort_by_key(|f| (f.team, f.match_number));
+
+    // most reliable scouts first, and scouts without co-scouted matches at the end
+    let mut scouts: Vec<ScoutAgreement> = scouts.into_values().collect();
+    scouts.sort_by(|a, b| {
+        (b.compared > 0).cmp(&(a.compared > 0))
+            .then(b.rate().partial_cmp(&a.rate()).unwrap_or(std::cmp::Ordering::Equal))
+    });
+
+    // show the worst matches first since those are the ones worth checking
+    matches.sort_by_key(|m| std::cmp::Reverse(m.disagreements()));
+
+    QualityReport { scouts, flagged, matches }
+}
+
+pub async fn get_quality_report(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+    let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.order_by((team.asc(), match_number.asc())).load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    let report = build_report(infos);
+
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(report.render().map_err(ErrorInternalServerError)?)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::Climb;
+    use crate::analysis::tests::entry;
+
+    fn reasons(report: &QualityReport) -> Vec<(i32, &str, &str)> {
+        report.flagged.iter().map(|f| (f.match_number, f.scout.as_str(), f.reason.as_str())).collect()
+    }
+
+    #[test]
+    fn counts_agree_within_the_tolerance() {
+        assert!(FieldValue::Count(4).agrees_with(&FieldValue::Count(4)));
+        assert!(FieldValue::Count(4).agrees_with(&FieldValue::Count(5)));
+        assert!(FieldValue::Count(4).agrees_with(&FieldValue::Count(3)));
+        assert!(!FieldValue::Count(4).agrees_with(&FieldValue::Count(6)));
+    }
+
+    #[test]
+    fn choices_have_to_match() {
+        let high = FieldValue::Choice("High".to_string());
+        assert!(high.agrees_with(&FieldValue::Choice("High".to_string())));
+        assert!(!high.agrees_with(&FieldValue::Choice("Mid".to_string())));
+        assert!(!high.agrees_with(&FieldValue::Count(0)));
+    }
+
+    #[test]
+    fn scouts_get_credit_for_fields_they_agree_on() {
+        let report = build_report(vec![
+            entry(33, 1, "Alex", 5, Climb::High, None),
+            entry(33, 1, "Sam", 6, Climb::Mid, None),
+            entry(33, 2, "Sam", 3, Climb::Low, None),
+        ]);
+        let alex = report.scouts.iter().find(|s| s.scout == "Alex").unwrap();
+        let sam = report.scouts.iter().find(|s| s.scout == "Sam").unwrap();
+        assert_eq!((alex.entries, alex.compared, alex.agreed), (1, 10, 9));
+        assert_eq!((sam.entries, sam.compared, sam.agreed), (2, 10, 9));
+
+        assert_eq!(report.matches.len(), 1);
+        assert_eq!(report.matches[0].disagreements(), 1);
+        let flagged = reasons(&report);
+        assert_eq!(flagged.len(), 2);
+        assert!(flagged.iter().all(|(m, _, reason)| *m == 1 && *reason == "disagrees with co-scout on Climb"));
+    }
+
+    #[test]
+    fn one_miscount_counts_against_every_co_scout() {
+        let report = build_report(vec![
+            entry(33, 1, "Alex", 5, Climb::High, None),
+            entry(33, 1, "Sam", 5, Climb::High, None),
+            entry(33, 1, "Kim", 9, Climb::High, None),
+        ]);
+        // a field only agrees when it's within the tolerance of every co-scout, so one miscount costs everyone
+        let agreed = |name: &str| report.scouts.iter().find(|s| s.scout == name).unwrap().agreed;
+        assert_eq!(agreed("Alex"), 9);
+        assert_eq!(agreed("Sam"), 9);
+        assert_eq!(agreed("Kim"), 9);
+        assert_eq!(report.matches[0].fields[5].values, vec!["5", "5", "9"]);
+        assert!(!report.matches[0].fields[5].agrees);
+    }
+
+    #[test]
+    fn entries_far_from_the_mean_are_flagged() {
+        let mut infos: Vec<RobotMatchInfo> = (1..=5).map(|m| entry(33, m, "Sam", 5, Climb::No, None)).collect();
+        infos.push(entry(33, 6, "Sam", 20, Climb::No, None));
+        let report = build_report(infos);
+        let flagged = reasons(&report);
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].0, 6);
+        assert!(flagged[0].2.starts_with("40 points is far from the team's usual 15.0"));
+    }
+
+    #[test]
+    fn co_scouted_matches_count_once_toward_the_mean() {
+        // counted twice the 40 point match would pull the mean up enough to hide it
+        let mut infos: Vec<RobotMatchInfo> = (1..=5).map(|m| entry(33, m, "Sam", 5, Climb::No, None)).collect();
+        infos.push(entry(33, 6, "Sam", 20, Climb::No, None));
+        infos.push(entry(33, 6, "Alex", 20, Climb::No, None));
+        let report = build_report(infos);
+        let flagged = reasons(&report);
+        assert_eq!(flagged.len(), 2);
+        assert!(flagged.iter().all(|(m, _, _)| *m == 6));
+    }
+
+    #[test]
+    fn outliers_need_enough_matches_and_some_spread() {
+        let few = vec![
+            entry(33, 1, "Sam", 0, Climb::No, None),
+            entry(33, 2, "Sam", 0, Climb::No, None),
+            entry(33, 3, "Sam", 30, Climb::No, None),
+        ];
+        assert!(build_report(few).flagged.is_empty());
+
+        let same: Vec<RobotMatchInfo> = (1..=6).map(|m| entry(33, m, "Sam", 5, Climb::No, None)).collect();
+        assert!(build_report(same).flagged.is_empty());
+    }
+}
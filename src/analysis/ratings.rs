@@ -0,0 +1,178 @@
+use std::{ops::AddAssign, collections::BTreeMap, cmp::Ordering};
+use diesel::prelude::*;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use askama::Template;
+use serde::Deserialize;
+use crate::{HttpResult, models::RobotMatchInfo, DbPool, DatabaseError, validation::RATINGS};
+use super::points::TeamPointsInfo;
+use super::{MatchAverage, by_team};
+
+/// Running total of one rating, leaving out the entries where it wasn't rated
+#[derive(Clone, Copy, Default)]
+pub(super) struct Rating {
+    sum: f32,
+    count: u32,
+}
+
+impl Rating {
+    fn new(value: Option<i32>) -> Self {
+        match value {
+            Some(v) => Rating { sum: v as f32, count: 1 },
+            None => Rating::default(),
+        }
+    }
+
+    pub(super) fn mean(&self) -> Option<f32> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.sum / (self.count as f32))
+        }
+    }
+
+    /// one rating for a match from whichever scouts rated it
+    fn averaged(self) -> Self {
+        match self.mean() {
+            Some(mean) => Rating { sum: mean, count: 1 },
+            None => Rating::default(),
+        }
+    }
+
+    /// the mean for a table cell, or a dash if nobody rated it
+    pub(super) fn show(&self) -> String {
+        self.mean().map(|m| format!("{:.1}", m)).unwrap_or_else(|| "–".to_string())
+    }
+}
+
+impl AddAssign for Rating {
+    fn add_assign(&mut self, rhs: Self) {
+        self.sum += rhs.sum;
+        self.count += rhs.count;
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct RatingsInfo {
+    pub(super) driver_skill: Rating,
+    pub(super) defense_played: Rating,
+    pub(super) defense_resilience: Rating,
+    pub(super) speed: Rating,
+    pub(super) penalties_drawn: Rating,
+}
+
+impl From<RobotMatchInfo> for RatingsInfo {
+    fn from(i: RobotMatchInfo) -> Self {
+        RatingsInfo {
+            driver_skill: Rating::new(i.driver_skill),
+            defense_played: Rating::new(i.defense_played),
+            defense_resilience: Rating::new(i.defense_resilience),
+            speed: Rating::new(i.speed),
+            penalties_drawn: Rating::new(i.penalties_drawn),
+        }
+    }
+}
+
+impl AddAssign for RatingsInfo {
+    fn add_assign(&mut self, rhs: Self) {
+        self.driver_skill += rhs.driver_skill;
+        self.defense_played += rhs.defense_played;
+        self.defense_resilience += rhs.defense_resilience;
+        self.speed += rhs.speed;
+        self.penalties_drawn += rhs.penalties_drawn;
+    }
+}
+
+impl MatchAverage for RatingsInfo {
+    fn averaged(self, _entries: u32) -> Self {
+        RatingsInfo {
+            driver_skill: self.driver_skill.averaged(),
+            defense_played: self.defense_played.averaged(),
+            defense_resilience: self.defense_resilience.averaged(),
+            speed: self.speed.averaged(),
+            penalties_drawn: self.penalties_drawn.averaged(),
+        }
+    }
+}
+
+impl RatingsInfo {
+    /// look up a rating by its form field name
+    pub(super) fn get(&self, field: &str) -> Rating {
+        match field {
+            "driver_skill" => self.driver_skill,
+            "defense_played" => self.defense_played,
+            "defense_resilience" => self.defense_resilience,
+            "speed" => self.speed,
+            "penalties_drawn" => self.penalties_drawn,
+            _ => Rating::default(),
+        }
+    }
+}
+
+pub struct TeamRatings {
+    team: i32,
+    points: TeamPointsInfo,
+    ratings: RatingsInfo,
+}
+
+impl TeamRatings {
+    /// the value to sort by for a column, higher is better except for the team number
+    fn sort_key(&self, column: &str) -> f32 {
+        match column {
+            "team" => -(self.team as f32),
+            "auto" => self.points.mean_auto(),
+            "teleop" => self.points.mean_tele(),
+            "climb" => self.points.mean_climb(),
+            "points" => self.points.mean_total(),
+            // unrated teams go to the bottom
+            field => self.ratings.get(field).mean().unwrap_or(-1f32),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct RatingsQuery {
+    #[serde(default)]
+    sort: String,
+}
+
+#[derive(Template)]
+#[template(path = "ratings.html")]
+pub struct RatingsTable {
+    sort: String,
+    teams: Vec<TeamRatings>,
+}
+
+impl RatingsTable {
+    fn ratings(&self) -> &'static [(&'static str, &'static str, &'static str)] {
+        &RATINGS
+    }
+
+    fn sort_marker(&self, column: &str) -> &str {
+        if self.sort == column { " ▼" } else { "" }
+    }
+}
+
+/// average points next to the average ratings, so good defenders stand out even when they don't score
+pub async fn get_ratings_table(pool: web::Data<DbPool>, query: web::Query<RatingsQuery>) -> HttpResult<HttpResponse> {
+    let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.order_by(team.asc()).load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    let points: BTreeMap<i32, TeamPointsInfo> = by_team(infos.iter().cloned());
+    let mut ratings: BTreeMap<i32, RatingsInfo> = by_team(infos);
+
+    let sort = if query.sort.is_empty() { "points".to_string() } else { query.into_inner().sort };
+    let mut teams: Vec<TeamRatings> = points.into_iter()
+        .map(|(team, points)| TeamRatings { team, points, ratings: ratings.remove(&team).unwrap_or_default() })
+        .collect();
+    teams.sort_by(|a, b| b.sort_key(&sort).partial_cmp(&a.sort_key(&sort)).unwrap_or(Ordering::Equal));
+
+    let table = RatingsTable { sort, teams };
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(table.render().map_err(ErrorInternalServerError)?)
+    )
+}
@@ -1,22 +1,289 @@
+use std::collections::BTreeMap;
 use diesel::prelude::*;
+use diesel::sqlite::Sqlite;
 use actix_web::{HttpResponse, web, error::ErrorInternalServerError};
 use askama::Template;
+use serde::{Deserialize, Serialize};
 
-use crate::{DbPool, HttpResult, DataListing, DatabaseError, RobotMatchInfo};
+use crate::{DbPool, HttpResult, DatabaseError, RobotMatchInfo};
+use crate::schema::{data, Alliance, Climb};
+use super::points::match_points;
 
-pub async fn get_data_listing(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+const PAGE_SIZE: i64 = 50;
+
+// sortable columns as (query value, heading)
+const COLUMNS: [(&str, &str); 19] = [
+    ("team", "Team"),
+    ("match_number", "Match Number"),
+    ("alliance", "Alliance"),
+    ("left_tarmac", "Left Tarmac"),
+    ("auto_high_made", "Auto High Made"),
+    ("auto_high_missed", "Auto High Missed"),
+    ("auto_low_made", "Auto Low Made"),
+    ("auto_low_missed", "Auto Low Missed"),
+    ("teleop_high_made", "Teleop High Made"),
+    ("teleop_high_missed", "Teleop High Missed"),
+    ("teleop_low_made", "Teleop Low Made"),
+    ("teleop_low_missed", "Teleop Low Missed"),
+    ("climb", "Climb"),
+    ("driver_skill", "Driver Skill"),
+    ("defense_played", "Defense Played"),
+    ("defense_resilience", "Defense Resilience"),
+    ("speed", "Speed"),
+    ("penalties_drawn", "Penalties Drawn"),
+    ("scout", "Scout"),
+];
+
+/// Query parameters for the data listing.
+/// These are all text because empty inputs in the filter form still get sent,
+/// and those should just mean "don't filter on this".
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct DataQuery {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    team: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    match_min: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    match_max: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    alliance: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    climb: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    notes: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    sort: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    dir: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    page: String,
+}
+
+impl DataQuery {
+    fn descending(&self) -> bool {
+        self.dir == "desc"
+    }
+
+    fn page(&self) -> i64 {
+        self.page.parse().unwrap_or(1).max(1)
+    }
+
+    fn sort_column(&self) -> &str {
+        if self.sort.is_empty() { "team" } else { &self.sort }
+    }
+
+    /// the query with all the filters applied but no ordering
+    fn filtered(&self) -> data::BoxedQuery<'static, Sqlite> {
+        use crate::schema::data::dsl::*;
+        let mut query = data.into_boxed();
+
+        if let Ok(n) = self.team.trim().parse::<i32>() {
+            query = query.filter(team.eq(n));
+        }
+        if let Ok(n) = self.match_min.trim().parse::<i32>() {
+            query = query.filter(match_number.ge(n));
+        }
+        if let Ok(n) = self.match_max.trim().parse::<i32>() {
+            query = query.filter(match_number.le(n));
+        }
+        if let Ok(a) = self.alliance.parse::<Alliance>() {
+            query = query.filter(alliance.eq(a));
+        }
+        if let Ok(c) = self.climb.parse::<Climb>() {
+            query = query.filter(climb.eq(c));
+        }
+        if !self.notes.trim().is_empty() {
+            // escape the wildcards so searching for "100%" does what it looks like
+            let escaped = self.notes.trim().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            query = query.filter(notes.like(format!("%{}%", escaped)).escape('\\'));
+        }
+
+        query
+    }
+
+    /// the filtered query ordered by the chosen column, with team and match to break ties
+    fn sorted(&self) -> data::BoxedQuery<'static, Sqlite> {
+        use crate::schema::data::dsl::*;
+
+        macro_rules! order {
+            ($query:expr, $column:expr) => {
+                if self.descending() { $query.order_by($column.desc()) } else { $query.order_by($column.asc()) }
+            };
+        }
+
+        let query = self.filtered();
+        let query = match self.sort_column() {
+            "match_number" => order!(query, match_number),
+            "alliance" => order!(query, alliance),
+            "left_tarmac" => order!(query, left_tarmac),
+            "auto_high_made" => order!(query, auto_high_made),
+            "auto_high_missed" => order!(query, auto_high_missed),
+            "auto_low_made" => order!(query, auto_low_made),
+            "auto_low_missed" => order!(query, auto_low_missed),
+            "teleop_high_made" => order!(query, teleop_high_made),
+            "teleop_high_missed" => order!(query, teleop_high_missed),
+            "teleop_low_made" => order!(query, teleop_low_made),
+            "teleop_low_missed" => order!(query, teleop_low_missed),
+            "climb" => order!(query, climb),
+            "driver_skill" => order!(query, driver_skill),
+            "defense_played" => order!(query, defense_played),
+            "defense_resilience" => order!(query, defense_resilience),
+            "speed" => order!(query, speed),
+            "penalties_drawn" => order!(query, penalties_drawn),
+            "scout" => order!(query, scout),
+            _ => order!(query, team),
+        };
+        query.then_order_by(team.asc()).then_order_by(match_number.asc())
+    }
+
+    fn to_link(&self) -> String {
+        format!("/data?{}", serde_urlencoded::to_string(self).unwrap_or_default())
+    }
+}
+
+/// Totals for a team over every entry that matches the filters
+#[derive(Default)]
+pub struct TeamSubtotal {
+    team: i32,
+    entries: i32,
+    auto_high_made: i32,
+    auto_high_missed: i32,
+    auto_low_made: i32,
+    auto_low_missed: i32,
+    teleop_high_made: i32,
+    teleop_high_missed: i32,
+    teleop_low_made: i32,
+    teleop_low_missed: i32,
+    points: i32,
+}
+
+impl TeamSubtotal {
+    fn add(&mut self, i: &RobotMatchInfo) {
+        self.team = i.team;
+        self.entries += 1;
+        self.auto_high_made += i.auto_high_made;
+        self.auto_high_missed += i.auto_high_missed;
+        self.auto_low_made += i.auto_low_made;
+        self.auto_low_missed += i.auto_low_missed;
+        self.teleop_high_made += i.teleop_high_made;
+        self.teleop_high_missed += i.teleop_high_missed;
+        self.teleop_low_made += i.teleop_low_made;
+        self.teleop_low_missed += i.teleop_low_missed;
+        self.points += match_points(i);
+    }
+
+    fn mean_points(&self) -> f32 {
+        (self.points as f32) / (self.entries as f32)
+    }
+}
+
+pub enum Row {
+    Entry(RobotMatchInfo),
+    Subtotal(TeamSubtotal),
+}
+
+#[derive(Template)]
+#[template(path = "data-listing.html")]
+pub struct DataListing {
+    query: DataQuery,
+    rows: Vec<Row>,
+    total: i64,
+    page: i64,
+    pages: i64,
+}
+
+impl DataListing {
+    fn columns(&self) -> &'static [(&'static str, &'static str)] {
+        &COLUMNS
+    }
+
+    fn sort_link(&self, column: &str) -> String {
+        let mut query = self.query.clone();
+        // clicking the column that's already sorted flips the direction
+        query.dir = if self.query.sort_column() == column && !self.query.descending() { "desc".to_string() } else { String::new() };
+        query.sort = column.to_string();
+        query.page = String::new();
+        query.to_link()
+    }
+
+    fn sort_marker(&self, column: &str) -> &str {
+        match (self.query.sort_column() == column, self.query.descending()) {
+            (true, false) => " ▲",
+            (true, true) => " ▼",
+            _ => "",
+        }
+    }
+
+    fn page_link(&self, page: i64) -> String {
+        let mut query = self.query.clone();
+        query.page = page.to_string();
+        query.to_link()
+    }
+
+    fn page(&self) -> i64 {
+        self.page
+    }
+
+    fn rating(&self, value: &Option<i32>) -> String {
+        value.map(|v| v.to_string()).unwrap_or_default()
+    }
+}
+
+pub async fn get_data_listing(pool: web::Data<DbPool>, query: web::Query<DataQuery>) -> HttpResult<HttpResponse> {
+    let query = query.into_inner();
     let data_listing: DataListing = web::block(move || -> Result<DataListing, DatabaseError> {
         use crate::schema::data::dsl::*;
         let conn = pool.get()?;
-        let results: Vec<RobotMatchInfo> = data.order_by(team.asc()).load(&conn)?;
-        let listing: DataListing = DataListing{ data: results };
-        Ok(listing)
+
+        let total: i64 = query.filtered().count().get_result(&conn)?;
+        let pages = ((total + PAGE_SIZE - 1) / PAGE_SIZE).max(1);
+        // a page past the end (or a huge number typed into the url) shows the last page
+        let page = query.page().min(pages);
+        let offset = (page - 1).saturating_mul(PAGE_SIZE);
+        let results: Vec<RobotMatchInfo> = query.sorted()
+            .offset(offset)
+            .limit(PAGE_SIZE)
+            .load(&conn)?;
+
+        let mut rows = Vec::new();
+        if query.sort_column() == "team" {
+            // subtotals only make sense when each team's rows are next to each other
+            let page_teams: Vec<i32> = results.iter().map(|i| i.team).collect();
+            let team_entries: Vec<RobotMatchInfo> = query.filtered().filter(team.eq_any(page_teams)).load(&conn)?;
+            let mut subtotals: BTreeMap<i32, TeamSubtotal> = BTreeMap::new();
+            for info in team_entries.iter() {
+                subtotals.entry(info.team).or_default().add(info);
+            }
+
+            // the last team on the page can carry on to the next one, which shows its subtotal instead
+            let next_page_team = query.sorted()
+                .offset(offset + PAGE_SIZE)
+                .limit(1)
+                .load::<RobotMatchInfo>(&conn)?
+                .first()
+                .map(|next| next.team);
+
+            let mut results = results.into_iter().peekable();
+            while let Some(info) = results.next() {
+                let current_team = info.team;
+                rows.push(Row::Entry(info));
+                if results.peek().map_or(next_page_team, |next| Some(next.team)) != Some(current_team) {
+                    if let Some(subtotal) = subtotals.remove(&current_team) {
+                        rows.push(Row::Subtotal(subtotal));
+                    }
+                }
+            }
+        } else {
+            rows.extend(results.into_iter().map(Row::Entry));
+        }
+
+        Ok(DataListing { query, rows, total, page, pages })
     }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
 
     Ok(
         HttpResponse::Ok()
             .content_type("text/html; charset=utf-8")
-            .body(data_listing.render().map_err(|e| ErrorInternalServerError(e))?)
+            .body(data_listing.render().map_err(ErrorInternalServerError)?)
     )
 }
-
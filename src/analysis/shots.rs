@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use diesel::prelude::*;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use serde::Deserialize;
+use crate::{HttpResult, models::ShotLocation, DbPool, DatabaseError, schema::Phase};
+use super::options::ChartQuery;
+
+// the 2022 field in feet
+const FIELD_LENGTH: f32 = 54.0;
+const FIELD_WIDTH: f32 = 27.0;
+// 3 foot squares, about the size of a robot
+const GRID_COLUMNS: usize = 18;
+const GRID_ROWS: usize = 9;
+
+/// Which shots to show, on top of the usual chart options
+#[derive(Deserialize)]
+pub struct ShotQuery {
+    // Auto or Teleop, anything else shows both
+    #[serde(default)]
+    phase: String,
+}
+
+/// count the shots in each grid cell, keyed by (column, row)
+fn grid_counts(shots: &[ShotLocation]) -> BTreeMap<(usize, usize), u32> {
+    let mut counts = BTreeMap::new();
+    for shot in shots {
+        // a shot right on the far edge still belongs in the last cell
+        let column = ((shot.x * GRID_COLUMNS as f32) as usize).min(GRID_COLUMNS - 1);
+        let row = ((shot.y * GRID_ROWS as f32) as usize).min(GRID_ROWS - 1);
+        *counts.entry((column, row)).or_default() += 1;
+    }
+    counts
+}
+
+/// heatmap of where a team shoots from, on a drawing of the field
+pub async fn get_shot_heatmap(pool: web::Data<DbPool>, path: web::Path<i32>, shot_query: web::Query<ShotQuery>, query: web::Query<ChartQuery>) -> HttpResult<HttpResponse> {
+    let team_number = path.into_inner();
+    let phase_filter: Option<Phase> = shot_query.phase.parse().ok();
+    let locations: Vec<ShotLocation> = web::block(move || -> Result<Vec<ShotLocation>, DatabaseError> {
+        use crate::schema::shots::dsl::*;
+        let conn = pool.get()?;
+        let mut query = shots.filter(team.eq(team_number)).into_boxed();
+        if let Some(p) = phase_filter {
+            query = query.filter(phase.eq(p));
+        }
+        Ok(query.load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+
+    use charts::{Chart, HeatmapView, ScaleLinear, ColorScale};
+
+    // the middle of each cell, so the heatmap bins it into the same cell
+    let cell_data: Vec<(f32, f32, f32)> = grid_counts(&locations).into_iter()
+        .map(|((column, row), count)| (
+            (column as f32 + 0.5) * FIELD_LENGTH / GRID_COLUMNS as f32,
+            (row as f32 + 0.5) * FIELD_WIDTH / GRID_ROWS as f32,
+            count as f32,
+        ))
+        .collect();
+    let most_shots = cell_data.iter().map(|(_, _, count)| *count).fold(1f32, f32::max);
+
+    // Define chart related sizes, with the inside the same shape as the field so the cells come out square.
+    // The field keeps its shape, so it fills whichever of the width and height runs out first.
+    let (top, right, bottom, left) = (90, 40, 60, 60);
+    let (max_width, max_height) = query.size(1100, 650);
+    let inner_width = (max_width - left - right)
+        .min((max_height - top - bottom) * 2)
+        .max(GRID_COLUMNS as isize * 2);
+    let inner_height = inner_width / 2;
+    let width = inner_width + left + right;
+    let height = inner_height + top + bottom;
+
+    let x = ScaleLinear::new()
+        .set_domain(vec![0_f32, FIELD_LENGTH])
+        .set_range(vec![0, inner_width]);
+
+    // the field diagram on the form has y going down, so keep it that way here
+    let y = ScaleLinear::new()
+        .set_domain(vec![0_f32, FIELD_WIDTH])
+        .set_range(vec![0, inner_height]);
+
+    // each cell is colored by how many shots came from it
+    let view = HeatmapView::new()
+        .set_x_scale(&x)
+        .set_y_scale(&y)
+        .set_bin_size(FIELD_LENGTH / GRID_COLUMNS as f32, FIELD_WIDTH / GRID_ROWS as f32)
+        .set_color_scale(ColorScale::yellow_orange_red())
+        .set_color_domain(vec![0f32, most_shots])
+        .set_custom_data_label(String::from("shots"))
+        .load_data(&cell_data).map_err(ErrorInternalServerError)?;
+
+    let title = match phase_filter {
+        Some(p) => format!("Team {} {} Shot Locations ({} shots)", team_number, p, locations.len()),
+        None => format!("Team {} Shot Locations ({} shots)", team_number, locations.len()),
+    };
+
+    // Generate and save the chart.
+    let svg_content = Chart::new()
+        .set_width(width)
+        .set_height(height)
+        .set_margins(top, right, bottom, left)
+        .add_title(title)
+        .add_legend_at(charts::AxisPosition::Top)
+        .add_view(view)
+        .add_axis_bottom(&x)
+        .add_axis_left(&y)
+        .add_left_axis_label("Feet")
+        .add_bottom_axis_label("Feet from the red alliance wall")
+        .to_svg().map_err(ErrorInternalServerError)?;
+
+    // draw the field lines underneath the chart so the cells can be placed on the field
+    let field = {
+        use svg::node::element::{Circle, Group, Line, Rectangle};
+        let feet = inner_width as f32 / FIELD_LENGTH;
+        Group::new()
+            .set("transform", format!("translate({},{})", left, top))
+            .add(Rectangle::new()
+                .set("width", inner_width / 2).set("height", inner_height)
+                .set("fill", "#f6e4e4"))
+            .add(Rectangle::new()
+                .set("x", inner_width / 2).set("width", inner_width / 2).set("height", inner_height)
+                .set("fill", "#e4e8f6"))
+            .add(Line::new()
+                .set("x1", inner_width / 2).set("y1", 0)
+                .set("x2", inner_width / 2).set("y2", inner_height)
+                .set("stroke", "#999"))
+            .add(Circle::new()
+                .set("cx", inner_width / 2).set("cy", inner_height / 2).set("r", 4.5 * feet)
+                .set("fill", "none").set("stroke", "#999"))
+    };
+
+    let document = svg::Document::new()
+        .set("width", width)
+        .set("height", height)
+        .set("viewBox", (0i32, 0i32, width, height))
+        .add(field)
+        .add(svg_content);
+
+    query.respond_document(document).await
+}
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use diesel::prelude::*;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use askama::Template;
+use serde::Deserialize;
+use crate::{HttpResult, models::{RobotMatchInfo, ScheduledMatch}, DbPool, DatabaseError, OUR_TEAM};
+use super::{compare::CompareInfo, points::TeamPointsInfo, by_match, by_team, show_count};
+
+const RECENT_NOTES: usize = 3;
+const SPARKLINE_WIDTH: f32 = 160.0;
+const SPARKLINE_HEIGHT: f32 = 40.0;
+const SPARKLINE_PADDING: f32 = 3.0;
+const MAX_SPARKLINE_SIZE: isize = 1000;
+
+#[derive(Deserialize)]
+pub struct StrategyQuery {
+    // chosen by hand, for matches that aren't in the schedule or to override it
+    #[serde(default)]
+    red: String,
+    #[serde(default)]
+    blue: String,
+}
+
+/// Everything the drive team gets about one robot
+pub struct RobotSummary {
+    team: i32,
+    matches: u32,
+    auto: f32,
+    teleop: f32,
+    climb: f32,
+    climbs: String,
+    accuracy: String,
+    cargo: f32,
+    notes: Vec<String>,
+}
+
+/// One alliance's robots, with a heading saying whose side they're on
+pub struct AllianceSummary {
+    title: String,
+    color: &'static str,
+    robots: Vec<RobotSummary>,
+}
+
+#[derive(Template)]
+#[template(path = "strategy.html")]
+pub struct StrategyPage {
+    match_number: i32,
+    red_input: String,
+    blue_input: String,
+    error: Option<String>,
+    // whether the teams came from the schedule rather than the form
+    scheduled: bool,
+    alliances: Vec<AllianceSummary>,
+}
+
+/// the rungs a team has reached and how often, best first
+fn climb_levels(info: &CompareInfo) -> String {
+    let levels: Vec<String> = [
+        (info.climb.traverse_climbs, "Traversal"),
+        (info.climb.high_climbs, "High"),
+        (info.climb.mid_climbs, "Mid"),
+        (info.climb.low_climbs, "Low"),
+        (info.climb.fails, "Failed"),
+    ].iter()
+        .filter(|(count, _)| *count > 0f32)
+        .map(|(count, level)| format!("{} {}", level, show_count(*count)))
+        .collect();
+    if levels.is_empty() {
+        "Never tried".to_string()
+    } else {
+        levels.join(", ")
+    }
+}
+
+fn summarize(team: i32, infos: &[RobotMatchInfo]) -> RobotSummary {
+    let info: CompareInfo = by_team(infos.iter().filter(|i| i.team == team).cloned())
+        .remove(&team)
+        .unwrap_or_default();
+    if info.points.num_matches == 0 {
+        return RobotSummary {
+            team,
+            matches: 0,
+            auto: 0f32,
+            teleop: 0f32,
+            climb: 0f32,
+            climbs: "–".to_string(),
+            accuracy: "–".to_string(),
+            cargo: 0f32,
+            notes: Vec::new(),
+        };
+    }
+
+    // infos come newest match first
+    let notes = infos.iter()
+        .filter(|i| i.team == team && !i.notes.trim().is_empty())
+        .take(RECENT_NOTES)
+        .map(|i| format!("Q{}: {}", i.match_number, i.notes.trim()))
+        .collect();
+
+    RobotSummary {
+        team,
+        matches: info.points.num_matches,
+        auto: info.points.mean_auto(),
+        teleop: info.points.mean_tele(),
+        climb: info.points.mean_climb(),
+        climbs: climb_levels(&info),
+        accuracy: info.accuracy.overall().map(|a| format!("{:.0}%", a * 100f32)).unwrap_or_else(|| "–".to_string()),
+        cargo: info.accuracy.cargo_per_match(),
+        notes,
+    }
+}
+
+/// one page summary of every other robot in a match, meant to be printed for the drive team
+pub async fn get_strategy_sheet(pool: web::Data<DbPool>, path: web::Path<i32>, query: web::Query<StrategyQuery>) -> HttpResult<HttpResponse> {
+    let match_number = path.into_inner();
+    let query = query.into_inner();
+    let mut page = StrategyPage {
+        match_number,
+        red_input: query.red.clone(),
+        blue_input: query.blue.clone(),
+        error: None,
+        scheduled: false,
+        alliances: Vec::new(),
+    };
+
+    let manual = !query.red.trim().is_empty() || !query.blue.trim().is_empty();
+    let chosen = match (super::parse_teams(&query.red, 3), super::parse_teams(&query.blue, 3)) {
+        (Ok(red), Ok(blue)) => Some((red, blue)),
+        (Err(e), _) | (_, Err(e)) => {
+            page.error = Some(e);
+            None
+        },
+    };
+
+    let infos_pool = pool.clone();
+    let wanted_match = match_number;
+    let scheduled: Option<ScheduledMatch> = web::block(move || -> Result<Option<ScheduledMatch>, DatabaseError> {
+        use crate::schema::schedule::dsl::*;
+        let conn = pool.get()?;
+        Ok(schedule.find(wanted_match).first(&conn).optional()?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    let teams = match (chosen, scheduled) {
+        (Some(chosen), _) if manual => Some(chosen),
+        (_, Some(s)) if page.error.is_none() => {
+            page.scheduled = true;
+            page.red_input = s.red().map(|t| t.to_string()).join(", ");
+            page.blue_input = s.blue().map(|t| t.to_string()).join(", ");
+            Some((s.red().to_vec(), s.blue().to_vec()))
+        },
+        _ => {
+            if page.error.is_none() {
+                page.error = Some(format!("Match {} isn't in the schedule, so choose the teams below", match_number));
+            }
+            None
+        },
+    };
+
+    if let Some((red, blue)) = teams {
+        let wanted: Vec<i32> = red.iter().chain(blue.iter()).copied().collect();
+        let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+            use crate::schema::data::dsl::*;
+            let conn = infos_pool.get()?;
+            Ok(data.filter(team.eq_any(wanted)).order_by(match_number.desc()).load(&conn)?)
+        }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+        // our partners go first, and if we aren't in the match both alliances are shown as they are
+        let mut sides = vec![("Red alliance".to_string(), "red", red), ("Blue alliance".to_string(), "blue", blue)];
+        if let Some(ours) = sides.iter().position(|(_, _, teams)| teams.contains(&OUR_TEAM)) {
+            sides[ours].0 = format!("Partners ({})", sides[ours].1);
+            sides[1 - ours].0 = format!("Opponents ({})", sides[1 - ours].1);
+            if ours == 1 {
+                sides.swap(0, 1);
+            }
+        }
+        page.alliances = sides.into_iter()
+            .map(|(title, color, teams)| AllianceSummary {
+                title,
+                color,
+                robots: teams.into_iter()
+                    .filter(|t| *t != OUR_TEAM)
+                    .map(|t| summarize(t, &infos))
+                    .collect(),
+            })
+            .collect();
+    }
+
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(page.render().map_err(ErrorInternalServerError)?)
+    )
+}
+
+#[derive(Deserialize)]
+pub struct SparklineQuery {
+    // save it as a file instead of showing it
+    #[serde(default)]
+    download: bool,
+    // drawn at the usual size and scaled to these
+    width: Option<isize>,
+    height: Option<isize>,
+}
+
+/// tiny line of a team's points in each match, small enough to sit next to their numbers
+pub async fn get_sparkline(pool: web::Data<DbPool>, path: web::Path<i32>, query: web::Query<SparklineQuery>) -> HttpResult<HttpResponse> {
+    let team_number = path.into_inner();
+    let infos: Vec<RobotMatchInfo> = web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.filter(team.eq(team_number)).load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    // matches scouted more than once get averaged
+    let matches: BTreeMap<(i32, i32), TeamPointsInfo> = by_match(infos);
+    let totals: Vec<f32> = matches.values().map(|p| p.mean_total()).collect();
+
+    use svg::node::element::{Circle, Polyline, Title};
+    use svg::node::Text as TextNode;
+
+    let max = totals.iter().copied().fold(1f32, f32::max);
+    let step = (SPARKLINE_WIDTH - 2f32 * SPARKLINE_PADDING) / (totals.len().max(2) - 1) as f32;
+    let points: Vec<(f32, f32)> = totals.iter().enumerate()
+        .map(|(i, total)| (
+            SPARKLINE_PADDING + i as f32 * step,
+            SPARKLINE_HEIGHT - SPARKLINE_PADDING - total / max * (SPARKLINE_HEIGHT - 2f32 * SPARKLINE_PADDING),
+        ))
+        .collect();
+
+    let mut document = svg::Document::new()
+        .set("width", query.width.map(|w| w.clamp(1, MAX_SPARKLINE_SIZE) as f32).unwrap_or(SPARKLINE_WIDTH))
+        .set("height", query.height.map(|h| h.clamp(1, MAX_SPARKLINE_SIZE) as f32).unwrap_or(SPARKLINE_HEIGHT))
+        .set("viewBox", (0f32, 0f32, SPARKLINE_WIDTH, SPARKLINE_HEIGHT))
+        .add(Title::new().add(TextNode::new(format!("Team {} points per match, up to {:.0}", team_number, max))))
+        .add(Polyline::new()
+            .set("points", points.iter().map(|(x, y)| format!("{:.1},{:.1}", x, y)).collect::<Vec<_>>().join(" "))
+            .set("fill", "none")
+            .set("stroke", "#4e79a7")
+            .set("stroke-width", 1.5));
+    // mark the latest match so the trend is easy to read
+    if let Some((x, y)) = points.last() {
+        document = document.add(Circle::new()
+            .set("cx", *x).set("cy", *y).set("r", 2.5)
+            .set("fill", "#e15759"));
+    }
+
+    let mut response = HttpResponse::Ok();
+    response.content_type("image/svg+xml; charset=utf-8");
+    if query.download {
+        response.append_header(("Content-Disposition", format!("attachment; filename=\"{}-points.svg\"", team_number)));
+    }
+    Ok(response.body(document.to_string()))
+}
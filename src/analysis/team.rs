@@ -0,0 +1,135 @@
+use std::collections::{BTreeSet, HashMap};
+use diesel::prelude::*;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use askama::Template;
+use crate::{HttpResult, models::{RobotMatchInfo, PitReport, PitPhoto, MatchEvent}, DbPool, DatabaseError};
+use crate::pit::{ClimbClaim, TeamPitData, load_team_pit_data};
+use super::points::{TeamPointsInfo, match_points};
+use super::climb::ClimbInfo;
+use super::ratings::RatingsInfo;
+use super::timing::{TimingInfo, timing_by_team, show_seconds};
+use super::{by_team, show_count};
+
+// words too common in notes to say anything about a robot
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "but", "for", "with", "was", "were", "are", "has", "had", "have", "they", "them",
+    "their", "its", "this", "that", "then", "than", "from", "into", "very", "not", "did", "didnt",
+    "does", "doesnt", "just", "also", "too", "all", "out", "off", "got", "get", "our", "after", "before",
+    "when", "while", "could", "would", "some", "any", "one", "again", "robot", "team",
+];
+const MAX_TERMS: usize = 30;
+
+/// A word that shows up often in a team's notes
+pub struct Term {
+    word: String,
+    count: u32,
+    // font size in em, so the most common words stand out
+    size: f32,
+}
+
+pub struct MatchEntry {
+    match_number: i32,
+    scout: String,
+    points: i32,
+    climb: String,
+    notes: String,
+}
+
+#[derive(Template)]
+#[template(path = "team.html")]
+pub struct TeamPage {
+    team: i32,
+    matches_scouted: usize,
+    points: TeamPointsInfo,
+    climbs: ClimbInfo,
+    ratings: RatingsInfo,
+    timing: Option<TimingInfo>,
+    terms: Vec<Term>,
+    entries: Vec<MatchEntry>,
+    pit: Option<PitReport>,
+    photos: Vec<PitPhoto>,
+    climb_claim: Option<ClimbClaim>,
+}
+
+impl TeamPage {
+    fn seconds(&self, value: Option<f32>) -> String {
+        show_seconds(value)
+    }
+
+    fn count(&self, value: &f32) -> String {
+        show_count(*value)
+    }
+}
+
+/// count the interesting words across all of a team's notes
+fn frequent_terms<'a>(notes: impl Iterator<Item = &'a str>) -> Vec<Term> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for note in notes {
+        let words = note.split(|c: char| !c.is_alphanumeric() && c != '\'')
+            .map(|w| w.replace('\'', "").to_lowercase())
+            .filter(|w| w.chars().count() > 2 && !STOP_WORDS.contains(&w.as_str()));
+        for word in words {
+            *counts.entry(word).or_default() += 1;
+        }
+    }
+
+    let mut terms: Vec<(String, u32)> = counts.into_iter().collect();
+    terms.sort_by(|(a_word, a), (b_word, b)| b.cmp(a).then(a_word.cmp(b_word)));
+    terms.truncate(MAX_TERMS);
+
+    let max = terms.first().map(|(_, c)| *c).unwrap_or(1) as f32;
+    let mut terms: Vec<Term> = terms.into_iter()
+        .map(|(word, count)| Term { word, count, size: 0.9 + 1.6 * (count as f32) / max })
+        .collect();
+    // alphabetical reads more like a cloud than a ranking
+    terms.sort_by(|a, b| a.word.cmp(&b.word));
+    terms
+}
+
+pub async fn get_team_page(pool: web::Data<DbPool>, path: web::Path<i32>) -> HttpResult<HttpResponse> {
+    let team_number = path.into_inner();
+    let events_pool = pool.clone();
+    let (infos, pit) = web::block(move || -> Result<(Vec<RobotMatchInfo>, TeamPitData), DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        let infos = data.filter(team.eq(team_number)).order_by((match_number.asc(), scout.asc())).load(&conn)?;
+        Ok((infos, load_team_pit_data(team_number, &conn)?))
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    let events: Vec<MatchEvent> = web::block(move || -> Result<Vec<MatchEvent>, DatabaseError> {
+        use crate::schema::match_events::dsl::*;
+        let conn = events_pool.get()?;
+        Ok(match_events.filter(team.eq(team_number)).load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    // matches scouted more than once get averaged
+    let points: TeamPointsInfo = by_team(infos.iter().cloned()).remove(&team_number).unwrap_or_default();
+    let climbs: ClimbInfo = by_team(infos.iter().cloned()).remove(&team_number).unwrap_or_default();
+    let ratings: RatingsInfo = by_team(infos.iter().cloned()).remove(&team_number).unwrap_or_default();
+
+    let page = TeamPage {
+        team: team_number,
+        matches_scouted: infos.iter().map(|i| i.match_number).collect::<BTreeSet<i32>>().len(),
+        points,
+        climbs,
+        ratings,
+        timing: timing_by_team(&events).remove(&team_number),
+        terms: frequent_terms(infos.iter().map(|i| i.notes.as_str())),
+        entries: infos.iter().map(|i| MatchEntry {
+            match_number: i.match_number,
+            scout: i.scout.clone(),
+            points: match_points(i),
+            climb: i.climb.to_string(),
+            notes: i.notes.clone(),
+        }).collect(),
+        climb_claim: pit.report.as_ref().map(|p| ClimbClaim::new(p.climb.clone(), infos.iter().map(|i| &i.climb))),
+        pit: pit.report,
+        photos: pit.photos,
+    };
+
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(page.render().map_err(ErrorInternalServerError)?)
+    )
+}
@@ -0,0 +1,263 @@
+use std::{ops::AddAssign, collections::BTreeMap, cmp::Ordering};
+use diesel::prelude::*;
+use actix_web::{HttpResponse, error::ErrorInternalServerError, web};
+use askama::Template;
+use crate::{HttpResult, models::MatchEvent, DbPool, DatabaseError};
+use crate::schema::{EventKind, Phase, MATCH_SECONDS};
+
+// shots closer together than this are from the same cycle, since robots fire both cargo in a burst
+const CYCLE_GAP_SECONDS: f32 = 4.0;
+
+/// Timing from the live logs, added up over any number of matches
+#[derive(Clone, Default)]
+pub(super) struct TimingInfo {
+    pub(super) logged_matches: u32,
+    cycles: u32,
+    // time between the starts of consecutive teleop cycles
+    cycle_gaps: u32,
+    cycle_seconds: f32,
+    climb_starts: u32,
+    climb_start_seconds: f32,
+    climbs: u32,
+    climb_seconds: f32,
+    defense_seconds: f32,
+}
+
+impl From<&[MatchEvent]> for TimingInfo {
+    /// work out the timing for one robot in one match, the events have to be in the order they were logged
+    fn from(events: &[MatchEvent]) -> Self {
+        let mut timing = TimingInfo { logged_matches: 1, ..Default::default() };
+
+        let mut cycle_starts = Vec::new();
+        let mut last_shot: Option<f32> = None;
+        for event in events.iter().filter(|e| e.kind.is_shot() && Phase::at(e.time) == Phase::Teleop) {
+            if last_shot.map(|t| event.time - t > CYCLE_GAP_SECONDS).unwrap_or(true) {
+                cycle_starts.push(event.time);
+            }
+            last_shot = Some(event.time);
+        }
+        timing.cycles = cycle_starts.len() as u32;
+        timing.cycle_gaps = timing.cycles.saturating_sub(1);
+        timing.cycle_seconds = cycle_starts.windows(2).map(|w| w[1] - w[0]).sum();
+
+        // a climb that gets restarted is timed from the last start
+        let mut climb_start = None;
+        let mut defense_start = None;
+        for event in events.iter() {
+            match event.kind {
+                EventKind::ClimbStart => climb_start = Some(event.time),
+                EventKind::ClimbEnd => if let Some(start) = climb_start.take() {
+                    timing.climbs += 1;
+                    timing.climb_seconds += event.time - start;
+                },
+                EventKind::DefenseStart if defense_start.is_none() => defense_start = Some(event.time),
+                EventKind::DefenseEnd => if let Some(start) = defense_start.take() {
+                    timing.defense_seconds += event.time - start;
+                },
+                _ => {},
+            }
+        }
+        // still defending at the end of the match
+        if let Some(start) = defense_start {
+            timing.defense_seconds += (MATCH_SECONDS - start).max(0f32);
+        }
+
+        if let Some(start) = events.iter().rev().find(|e| e.kind == EventKind::ClimbStart).map(|e| e.time) {
+            timing.climb_starts = 1;
+            timing.climb_start_seconds = start;
+        }
+
+        timing
+    }
+}
+
+impl AddAssign for TimingInfo {
+    fn add_assign(&mut self, rhs: Self) {
+        self.logged_matches += rhs.logged_matches;
+        self.cycles += rhs.cycles;
+        self.cycle_gaps += rhs.cycle_gaps;
+        self.cycle_seconds += rhs.cycle_seconds;
+        self.climb_starts += rhs.climb_starts;
+        self.climb_start_seconds += rhs.climb_start_seconds;
+        self.climbs += rhs.climbs;
+        self.climb_seconds += rhs.climb_seconds;
+        self.defense_seconds += rhs.defense_seconds;
+    }
+}
+
+fn mean(total: f32, count: u32) -> Option<f32> {
+    if count == 0 {
+        None
+    } else {
+        Some(total / (count as f32))
+    }
+}
+
+impl TimingInfo {
+    pub(super) fn mean_cycle_time(&self) -> Option<f32> {
+        mean(self.cycle_seconds, self.cycle_gaps)
+    }
+
+    pub(super) fn cycles_per_match(&self) -> Option<f32> {
+        mean(self.cycles as f32, self.logged_matches)
+    }
+
+    /// seconds into the match the climb usually starts
+    pub(super) fn mean_climb_start(&self) -> Option<f32> {
+        mean(self.climb_start_seconds, self.climb_starts)
+    }
+
+    pub(super) fn mean_climb_duration(&self) -> Option<f32> {
+        mean(self.climb_seconds, self.climbs)
+    }
+
+    pub(super) fn defense_per_match(&self) -> Option<f32> {
+        mean(self.defense_seconds, self.logged_matches)
+    }
+}
+
+/// seconds to one decimal place for a table cell, or a dash if there's nothing to show
+pub(super) fn show_seconds(value: Option<f32>) -> String {
+    value.map(|v| format!("{:.1}", v)).unwrap_or_else(|| "–".to_string())
+}
+
+/// group events into the separate logs for each robot in each match and time them
+pub(super) fn timing_by_team(events: &[MatchEvent]) -> BTreeMap<i32, TimingInfo> {
+    let mut logs: BTreeMap<(i32, i32, &str), Vec<MatchEvent>> = BTreeMap::new();
+    for event in events {
+        logs.entry((event.team, event.match_number, &event.scout)).or_default().push(event.clone());
+    }
+
+    let mut teams: BTreeMap<i32, TimingInfo> = BTreeMap::new();
+    for ((team, _, _), mut log) in logs {
+        log.sort_by_key(|e| e.sequence);
+        *teams.entry(team).or_default() += TimingInfo::from(log.as_slice());
+    }
+    teams
+}
+
+pub struct TeamTiming {
+    team: i32,
+    timing: TimingInfo,
+}
+
+#[derive(Template)]
+#[template(path = "timing.html")]
+pub struct TimingTable {
+    teams: Vec<TeamTiming>,
+}
+
+impl TimingTable {
+    fn seconds(&self, value: Option<f32>) -> String {
+        show_seconds(value)
+    }
+}
+
+/// cycle times and climb timing for every team that's been scouted in live mode
+pub async fn get_timing_table(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+    let events: Vec<MatchEvent> = web::block(move || -> Result<Vec<MatchEvent>, DatabaseError> {
+        use crate::schema::match_events::dsl::*;
+        let conn = pool.get()?;
+        Ok(match_events.load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    let mut teams: Vec<TeamTiming> = timing_by_team(&events).into_iter()
+        .map(|(team, timing)| TeamTiming { team, timing })
+        .collect();
+    // fastest cyclers first, with the ones that never cycled at the end
+    teams.sort_by(|a, b| match (a.timing.mean_cycle_time(), b.timing.mean_cycle_time()) {
+        (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+
+    let table = TimingTable { teams };
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(table.render().map_err(ErrorInternalServerError)?)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(events: &[(EventKind, f32)]) -> Vec<MatchEvent> {
+        events.iter().enumerate()
+            .map(|(i, (kind, time))| MatchEvent {
+                team: 33,
+                match_number: 1,
+                scout: "Sam".to_string(),
+                sequence: i as i32,
+                time: *time,
+                kind: *kind,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn an_empty_log_has_no_timing() {
+        let timing = TimingInfo::from(log(&[]).as_slice());
+        assert_eq!(timing.logged_matches, 1);
+        assert_eq!(timing.cycles_per_match(), Some(0f32));
+        assert_eq!(timing.mean_cycle_time(), None);
+        assert_eq!(timing.mean_climb_start(), None);
+        assert_eq!(timing.mean_climb_duration(), None);
+        assert_eq!(timing.defense_per_match(), Some(0f32));
+    }
+
+    #[test]
+    fn bursts_of_shots_are_one_cycle() {
+        let timing = TimingInfo::from(log(&[
+            // auto shots don't count towards cycles
+            (EventKind::HighMade, 5.0),
+            (EventKind::HighMade, 20.0),
+            (EventKind::HighMissed, 21.0),
+            (EventKind::LowMade, 40.0),
+            // exactly the gap apart is still the same cycle
+            (EventKind::LowMade, 44.0),
+        ]).as_slice());
+        assert_eq!(timing.cycles_per_match(), Some(2f32));
+        assert_eq!(timing.mean_cycle_time(), Some(20f32));
+    }
+
+    #[test]
+    fn a_single_cycle_has_no_cycle_time() {
+        let timing = TimingInfo::from(log(&[(EventKind::HighMade, 30.0)]).as_slice());
+        assert_eq!(timing.cycles_per_match(), Some(1f32));
+        assert_eq!(timing.mean_cycle_time(), None);
+    }
+
+    #[test]
+    fn restarted_climbs_are_timed_from_the_last_start() {
+        let timing = TimingInfo::from(log(&[
+            (EventKind::ClimbStart, 110.0),
+            (EventKind::ClimbStart, 120.0),
+            (EventKind::ClimbEnd, 135.0),
+        ]).as_slice());
+        assert_eq!(timing.mean_climb_start(), Some(120f32));
+        assert_eq!(timing.mean_climb_duration(), Some(15f32));
+    }
+
+    #[test]
+    fn unfinished_defense_runs_to_the_end_of_the_match() {
+        let timing = TimingInfo::from(log(&[
+            (EventKind::DefenseStart, 30.0),
+            (EventKind::DefenseEnd, 40.0),
+            (EventKind::DefenseStart, MATCH_SECONDS - 5.0),
+        ]).as_slice());
+        assert_eq!(timing.defense_per_match(), Some(15f32));
+    }
+
+    #[test]
+    fn every_scouts_log_is_timed_separately() {
+        let mut events = log(&[(EventKind::HighMade, 20.0), (EventKind::HighMade, 60.0)]);
+        events.extend(log(&[(EventKind::HighMade, 22.0)]).into_iter().map(|e| MatchEvent { scout: "Alex".to_string(), ..e }));
+        let teams = timing_by_team(&events);
+        assert_eq!(teams[&33].logged_matches, 2);
+        assert_eq!(teams[&33].cycles_per_match(), Some(1.5));
+        assert_eq!(timing_by_team(&[]).len(), 0);
+    }
+}
@@ -0,0 +1,139 @@
+use diesel::prelude::*;
+use actix_web::{HttpResponse, web, error::ErrorInternalServerError};
+use askama::Template;
+use serde::Deserialize;
+
+use crate::{DbPool, HttpResult, DatabaseError, models::{EventTeam, ScheduledMatch}};
+
+#[derive(Template)]
+#[template(path = "event-teams.html")]
+pub struct EventTeamsPage {
+    teams: Vec<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct EventTeamsForm {
+    teams: String,
+}
+
+/// show the list of teams at the event with a form to replace it
+pub async fn get_event_teams(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+    let page = web::block(move || -> Result<EventTeamsPage, DatabaseError> {
+        use crate::schema::event_teams::dsl::*;
+        let conn = pool.get()?;
+        Ok(EventTeamsPage { teams: event_teams.select(team).order_by(team.asc()).load(&conn)? })
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(page.render().map_err(ErrorInternalServerError)?)
+    )
+}
+
+/// replace the event team list with the numbers in the submitted text
+pub async fn set_event_teams(pool: web::Data<DbPool>, params: web::Form<EventTeamsForm>) -> HttpResult<HttpResponse> {
+    // accept anything separated by whitespace or commas so a list can be pasted straight from a spreadsheet
+    let mut numbers = Vec::new();
+    for word in params.teams.split(|c: char| c.is_whitespace() || c == ',').filter(|w| !w.is_empty()) {
+        match word.parse::<i32>() {
+            Ok(n) if n > 0 => numbers.push(EventTeam { team: n }),
+            _ => return Ok(HttpResponse::BadRequest()
+                .content_type("text/plain; charset=utf-8")
+                .body(format!("\"{}\" isn't a team number", word))),
+        }
+    }
+    numbers.sort_by_key(|t| t.team);
+    numbers.dedup_by_key(|t| t.team);
+
+    web::block(move || -> Result<(), DatabaseError> {
+        use crate::schema::event_teams;
+        let conn = pool.get()?;
+        conn.transaction(|| {
+            diesel::delete(event_teams::table).execute(&conn)?;
+            // sqlite can't do batch inserts in diesel 1.x
+            for number in numbers.iter() {
+                diesel::insert_into(event_teams::table).values(number).execute(&conn)?;
+            }
+            Ok(())
+        })
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Found()
+        .append_header(("Location", "/teams")).finish())
+}
+
+#[derive(Template)]
+#[template(path = "schedule.html")]
+pub struct SchedulePage {
+    matches: Vec<ScheduledMatch>,
+}
+
+#[derive(Deserialize)]
+pub struct ScheduleForm {
+    schedule: String,
+}
+
+/// show the imported match schedule with a form to replace it
+pub async fn get_schedule(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+    let page = web::block(move || -> Result<SchedulePage, DatabaseError> {
+        use crate::schema::schedule::dsl::*;
+        let conn = pool.get()?;
+        Ok(SchedulePage { matches: schedule.order_by(match_number.asc()).load(&conn)? })
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(page.render().map_err(ErrorInternalServerError)?)
+    )
+}
+
+/// read one line of the schedule, the match number followed by the three red then three blue teams
+fn parse_scheduled_match(line: &str) -> Result<ScheduledMatch, String> {
+    let mut numbers = Vec::new();
+    for word in line.split(|c: char| c.is_whitespace() || c == ',').filter(|w| !w.is_empty()) {
+        match word.parse::<i32>() {
+            Ok(n) if n > 0 => numbers.push(n),
+            _ => return Err(format!("\"{}\" isn't a match or team number", word)),
+        }
+    }
+    match numbers[..] {
+        [match_number, red1, red2, red3, blue1, blue2, blue3] =>
+            Ok(ScheduledMatch { match_number, red1, red2, red3, blue1, blue2, blue3 }),
+        _ => Err(format!("\"{}\" should be a match number and six teams", line.trim())),
+    }
+}
+
+/// replace the schedule with the pasted one, one match per line
+pub async fn set_schedule(pool: web::Data<DbPool>, params: web::Form<ScheduleForm>) -> HttpResult<HttpResponse> {
+    let mut matches = Vec::new();
+    for line in params.schedule.lines().filter(|l| !l.trim().is_empty()) {
+        match parse_scheduled_match(line) {
+            Ok(m) => matches.push(m),
+            Err(e) => return Ok(HttpResponse::BadRequest()
+                .content_type("text/plain; charset=utf-8")
+                .body(e)),
+        }
+    }
+    matches.sort_by_key(|m| m.match_number);
+    // a match pasted twice keeps the later line
+    matches.reverse();
+    matches.dedup_by_key(|m| m.match_number);
+    matches.reverse();
+
+    web::block(move || -> Result<(), DatabaseError> {
+        use crate::schema::schedule;
+        let conn = pool.get()?;
+        conn.transaction(|| {
+            diesel::delete(schedule::table).execute(&conn)?;
+            for m in matches.iter() {
+                diesel::insert_into(schedule::table).values(m).execute(&conn)?;
+            }
+            Ok(())
+        })
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Found()
+        .append_header(("Location", "/schedule")).finish())
+}
@@ -0,0 +1,104 @@
+use diesel::prelude::*;
+use actix_web::{HttpResponse, web, error::ErrorInternalServerError};
+use askama::Template;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::{DbPool, HttpResult, DatabaseError, models::RobotMatchInfo};
+
+// how many submissions a slow client can fall behind before it starts missing some
+const CHANNEL_CAPACITY: usize = 64;
+// how many submissions to show when the feed page is first opened
+const FEED_HISTORY: i64 = 30;
+
+/// The part of a submission the scouting lead cares about when watching it come in
+#[derive(Serialize, Clone)]
+pub struct SubmissionSummary {
+    team: i32,
+    match_number: i32,
+    alliance: String,
+    scout: String,
+    points: i32,
+    climb: String,
+    notes: String,
+}
+
+impl From<&RobotMatchInfo> for SubmissionSummary {
+    fn from(i: &RobotMatchInfo) -> Self {
+        SubmissionSummary {
+            team: i.team,
+            match_number: i.match_number,
+            alliance: i.alliance.to_string(),
+            scout: i.scout.clone(),
+            points: crate::analysis::match_points(i),
+            climb: i.climb.to_string(),
+            notes: i.notes.clone(),
+        }
+    }
+}
+
+/// Sends every new submission to everyone with the live feed open
+#[derive(Clone)]
+pub struct Broadcaster {
+    sender: broadcast::Sender<String>,
+}
+
+impl Broadcaster {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Broadcaster { sender }
+    }
+
+    pub fn send(&self, info: &RobotMatchInfo) {
+        match serde_json::to_string(&SubmissionSummary::from(info)) {
+            // an error here just means nobody is listening
+            Ok(json) => { let _ = self.sender.send(json); },
+            Err(e) => log::error!("unable to serialize submission for the live feed: {}", e),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "live-feed.html")]
+pub struct LiveFeed {
+    recent: Vec<SubmissionSummary>,
+}
+
+pub async fn get_live_feed(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+    let feed = web::block(move || -> Result<LiveFeed, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        let results: Vec<RobotMatchInfo> = data.order_by(submitted_at.desc()).limit(FEED_HISTORY).load(&conn)?;
+        Ok(LiveFeed { recent: results.iter().map(SubmissionSummary::from).collect() })
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(feed.render().map_err(ErrorInternalServerError)?)
+    )
+}
+
+/// server-sent event stream of new submissions as JSON
+pub async fn get_feed_events(broadcaster: web::Data<Broadcaster>) -> HttpResponse {
+    let receiver = broadcaster.sender.subscribe();
+
+    let events = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(json) => {
+                    let event = web::Bytes::from(format!("data: {}\n\n", json));
+                    return Some((Ok::<_, actix_web::Error>(event), receiver));
+                },
+                // missed a few, but the newer ones are still worth sending
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .append_header(("Cache-Control", "no-cache"))
+        .streaming(events)
+}
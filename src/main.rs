@@ -1,3 +1,6 @@
+// diesel 1.x's macros put impls inside of consts, which newer compilers warn about
+#![allow(non_local_definitions)]
+
 // using macro_use syntax because a normal use didn't seem to be doing it
 // should probably figure out why and `use` the correct macros
 #[macro_use]
@@ -6,11 +9,13 @@ extern crate diesel;
 extern crate migrations_macros;
 use std::fmt::Debug;
 use actix_web::error::ErrorInternalServerError;
-use actix_web::{middleware, web, App, HttpResponse, HttpServer, Result as HttpResult};
+use actix_web::{middleware, web, App, HttpRequest, HttpResponse, HttpServer, Result as HttpResult};
+use actix_web::cookie::{Cookie, time::Duration as CookieDuration};
+use actix_web::http::header;
 use actix_web_httpauth::middleware::HttpAuthentication;
 use diesel::prelude::*;
 use diesel::sqlite::SqliteConnection;
-use diesel::r2d2::ConnectionManager;
+use diesel::r2d2::{ConnectionManager, CustomizeConnection};
 use diesel_migrations::embed_migrations;
 use log::{error, info};
 use askama::Template;
@@ -25,6 +30,19 @@ mod models;
 mod schema;
 mod auth;
 mod analysis;
+mod validation;
+mod event;
+mod feed;
+mod search;
+mod pit;
+
+// remembers who is scouting on this device so they don't have to type it every match
+const SCOUT_COOKIE: &str = "scout";
+// "team:match" of the last saved submission, shown once on the next page load
+const SAVED_COOKIE: &str = "saved";
+const RECENT_SUBMISSIONS: i64 = 10;
+// left off strategy sheets, since the drive team knows what their own robot does
+const OUR_TEAM: i32 = 8033;
 
 #[derive(thiserror::Error, Debug)]
 pub enum DatabaseError {
@@ -34,10 +52,36 @@ pub enum DatabaseError {
     DieselError(#[from] diesel::result::Error),
 }
 
+// sqlite leaves foreign keys off unless every connection turns them on,
+// without this deleting a submission would leave its shots and events behind
+#[derive(Debug)]
+struct ForeignKeys;
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for ForeignKeys {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        conn.execute("PRAGMA foreign_keys = ON").map(|_| ()).map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
 #[derive(Template)]
-#[template(path = "data-listing.html")]
-pub struct DataListing {
-    data: Vec<RobotMatchInfo>
+#[template(path = "form.html")]
+pub struct ScoutingForm {
+    form: validation::RawSubmission,
+    errors: validation::FormErrors,
+    saved: Option<String>,
+    recent: Vec<RobotMatchInfo>,
+}
+
+impl ScoutingForm {
+    fn ratings(&self) -> &'static [(&'static str, &'static str, &'static str)] {
+        &validation::RATINGS
+    }
+
+    fn to_response(&self, mut builder: actix_web::HttpResponseBuilder) -> HttpResult<HttpResponse> {
+        Ok(builder
+            .content_type("text/html; charset=utf-8")
+            .body(self.render().map_err(ErrorInternalServerError)?))
+    }
 }
 
 #[actix_web::main]
@@ -50,7 +94,10 @@ async fn main() -> std::io::Result<()> {
 
     let manager = ConnectionManager::<SqliteConnection>::new(database_url.clone());
 
+    let broadcaster = feed::Broadcaster::new();
+
     let pool = diesel::r2d2::Pool::builder()
+        .connection_customizer(Box::new(ForeignKeys))
         .build(manager)
         .expect("failed to create database connection pool");
 
@@ -58,20 +105,24 @@ async fn main() -> std::io::Result<()> {
     // If there is no database the connection will create one but it won't have the table until this runs
     if let Ok(conn) = pool.get() {
         use crate::schema::data::dsl::*;
-        if let Err(_) = data.load::<RobotMatchInfo>(&conn) {
-            info!("Unable to get data; database either doesn't exist or is empty or corrupted");
-            info!("Running migrations to create a valid database...");
-            embedded_migrations::run(&conn).expect("unable to run migrations to create database");
+        if data.load::<RobotMatchInfo>(&conn).is_err() {
+            info!("Unable to get data; database either doesn't exist, is from an older version, or is corrupted");
         } else {
             info!("Using existing database");
         }
+        // this only runs the migrations that haven't been applied yet, so it also upgrades older databases
+        info!("Running any pending migrations...");
+        embedded_migrations::run(&conn).expect("unable to run migrations to create database");
     } else {
         error!("Unable to connect to database to check table");
     }
 
+    std::fs::create_dir_all(pit::PHOTO_DIR)?;
+
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .app_data(web::Data::new(broadcaster.clone()))
             .wrap(middleware::Logger::default())
             .wrap(HttpAuthentication::basic(auth::check_password))
             .configure(app_config)
@@ -86,46 +137,144 @@ fn app_config(config: &mut web::ServiceConfig) {
         web::scope("")
             .service(web::resource("/").route(web::get().to(index)))
             .service(web::resource("/submit").route(web::post().to(handle_submit)))
+            .service(web::resource("/dashboard").route(web::get().to(analysis::get_dashboard)))
             .service(web::resource("/data").route(web::get().to(analysis::get_data_listing)))
             .service(web::resource("/points").route(web::get().to(analysis::get_points_chart)))
+            .service(web::resource("/points/box").route(web::get().to(analysis::get_points_box_chart)))
+            .service(web::resource("/points/violin").route(web::get().to(analysis::get_points_violin_chart)))
+            .service(web::resource("/points/histogram").route(web::get().to(analysis::get_points_histogram)))
+            .service(web::resource("/points/grid").route(web::get().to(analysis::get_points_grid)))
             .service(web::resource("/accuracy").route(web::get().to(analysis::get_accuracy_chart)))
+            .service(web::resource("/accuracy/table").route(web::get().to(analysis::get_accuracy_table)))
+            .service(web::resource("/accuracy/breakdown").route(web::get().to(analysis::get_accuracy_breakdown_chart)))
             .service(web::resource("/climb").route(web::get().to(analysis::get_climb_chart)))
+            .service(web::resource("/ratings").route(web::get().to(analysis::get_ratings_table)))
+            .service(web::resource("/timing").route(web::get().to(analysis::get_timing_table)))
+            .service(web::resource("/compare").route(web::get().to(analysis::get_comparison)))
+            .service(web::resource("/compare/matches").route(web::get().to(analysis::get_comparison_matches_chart)))
+            .service(web::resource("/compare/radar").route(web::get().to(analysis::get_comparison_radar)))
+            .service(web::resource("/strategy/{match}").route(web::get().to(analysis::get_strategy_sheet)))
+            .service(web::resource("/strategy/sparkline/{team}").route(web::get().to(analysis::get_sparkline)))
+            .service(web::resource("/plan").route(web::get().to(analysis::get_match_plan)))
+            .service(web::resource("/search").route(web::get().to(search::get_search)))
+            .service(web::resource("/team/{team}").route(web::get().to(analysis::get_team_page)))
+            .service(web::resource("/team/{team}/card").route(web::get().to(analysis::get_team_card)))
+            .service(web::resource("/team/{team}/shots").route(web::get().to(analysis::get_shot_heatmap)))
+            .service(web::resource("/team/{team}/climb").route(web::get().to(analysis::get_team_climb_donut)))
+            .service(web::resource("/quality").route(web::get().to(analysis::get_quality_report)))
+            .service(web::resource("/feed").route(web::get().to(feed::get_live_feed)))
+            .service(web::resource("/feed/events").route(web::get().to(feed::get_feed_events)))
+            .service(web::resource("/pit")
+                .route(web::get().to(pit::get_pit_form))
+                .route(web::post().to(pit::handle_pit_submit)))
+            .service(web::resource("/pit/claims").route(web::get().to(pit::get_pit_claims)))
+            .service(actix_files::Files::new("/photos", pit::PHOTO_DIR))
+            .service(web::resource("/teams")
+                .route(web::get().to(event::get_event_teams))
+                .route(web::post().to(event::set_event_teams)))
+            .service(web::resource("/schedule")
+                .route(web::get().to(event::get_schedule))
+                .route(web::post().to(event::set_schedule)))
     );
 }
 
+/// load the last few submissions from a scout so they can check what they've sent
+async fn load_recent(pool: web::Data<DbPool>, scout_name: String) -> HttpResult<Vec<RobotMatchInfo>> {
+    if scout_name.is_empty() {
+        return Ok(Vec::new());
+    }
+    web::block(move || -> Result<Vec<RobotMatchInfo>, DatabaseError> {
+        use crate::schema::data::dsl::*;
+        let conn = pool.get()?;
+        Ok(data.filter(scout.eq(scout_name))
+            .order_by(submitted_at.desc())
+            .limit(RECENT_SUBMISSIONS)
+            .load(&conn)?)
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)
+}
+
 // put the form on the main page
-async fn index() -> HttpResult<HttpResponse> {
-    Ok(HttpResponse::Ok()
-        .content_type("text/html; charset=utf-8")
-        .body(include_str!("../static/form.html")))
+async fn index(req: HttpRequest, pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+    let form = validation::RawSubmission {
+        scout: req.cookie(SCOUT_COOKIE).map(|c| c.value().to_string()).unwrap_or_default(),
+        ..Default::default()
+    };
+
+    let saved = req.cookie(SAVED_COOKIE)
+        .and_then(|c| c.value().split_once(':').map(|(t, m)| format!("Saved team {} match {}", t, m)));
+
+    let recent = load_recent(pool, form.scout.clone()).await?;
+
+    let mut response = ScoutingForm {
+        form,
+        errors: Default::default(),
+        saved,
+        recent,
+    }.to_response(HttpResponse::Ok())?;
+
+    // the confirmation should only show up once
+    if response.add_removal_cookie(&Cookie::named(SAVED_COOKIE)).is_err() {
+        error!("Unable to clear the saved submission cookie");
+    }
+    Ok(response)
 }
 
 /// handle POST request to submit data
-async fn handle_submit(pool: web::Data<DbPool>, params: web::Form<RobotMatchInfo>) -> HttpResult<HttpResponse> {
-    let params = params.into_inner();
-    let made = params.teleop_high_made + params.teleop_low_made;
-    let missed = params.teleop_high_missed + params.teleop_low_missed;
-    let total = made + missed;
-    let accuracy = (made as f64) / (total as f64);
+async fn handle_submit(pool: web::Data<DbPool>, broadcaster: web::Data<feed::Broadcaster>, params: web::Form<validation::RawSubmission>) -> HttpResult<HttpResponse> {
+    let form = params.into_inner();
+
+    // catch anything missing or obviously wrong before touching the database,
+    // and give the form back with the scout's values so nothing has to be counted again
+    let mut submission = match form.parse() {
+        Ok(submission) => submission,
+        Err(errors) => {
+            let recent = load_recent(pool, form.scout.trim().to_string()).await?;
+            return ScoutingForm { form, errors, saved: None, recent }.to_response(HttpResponse::BadRequest());
+        },
+    };
 
-    info!("Inserting team {} match {}", params.team, params.match_number);
+    submission.info.submitted_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let info = submission.info.clone();
 
-    use schema::*;
+    info!("Inserting team {} match {} from {} with {} shot locations", info.team, info.match_number, info.scout, submission.shots.len());
 
     // run the blocking database tasks (this probably puts it on its own thread)
-    web::block(move || -> Result<(), DatabaseError> {
-        // just connect to the connection pool and insert the values
-        let conn = pool.get()?;
-        diesel::insert_into(data::table)
-            .values(&params)
-            .execute(&conn)?;
-        Ok(())
-    })
-    // just turn everything into internal server errors
-    .await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+    let result = web::block({
+        let pool = pool.clone();
+        move || -> Result<(), validation::SubmitError> {
+            let conn = pool.get()?;
+            validation::validate_team(submission.info.team, &conn)?;
+            validation::insert_submission(&submission, &conn)
+        }
+    }).await.map_err(ErrorInternalServerError)?;
 
-    
-    Ok(HttpResponse::Found()
-        .append_header(("Location", "/")).finish())
-}
+    match result {
+        Ok(()) => {
+            broadcaster.send(&info);
 
+            let scout_cookie = Cookie::build(SCOUT_COOKIE, info.scout.clone())
+                .path("/")
+                .max_age(CookieDuration::days(30))
+                .finish();
+            let saved_cookie = Cookie::build(SAVED_COOKIE, format!("{}:{}", info.team, info.match_number))
+                .path("/")
+                .finish();
+
+            // set the headers directly so names with spaces get percent-encoded
+            Ok(HttpResponse::Found()
+                .append_header((header::SET_COOKIE, scout_cookie.encoded().to_string()))
+                .append_header((header::SET_COOKIE, saved_cookie.encoded().to_string()))
+                .append_header(("Location", "/")).finish())
+        },
+        Err(validation::SubmitError::Invalid(e)) => {
+            let recent = load_recent(pool, info.scout).await?;
+            ScoutingForm { form, errors: e.into(), saved: None, recent }
+                .to_response(HttpResponse::BadRequest())
+        },
+        // just turn everything else into internal server errors
+        Err(e) => Err(ErrorInternalServerError(e)),
+    }
+}
@@ -20,5 +20,89 @@ pub struct RobotMatchInfo {
     pub teleop_low_missed: i32,
     pub climb: Climb,
     pub notes: String,
+    pub scout: String,
+    pub submitted_at: i64,
+    // 1 to 5, or None if the scout didn't rate it
+    pub driver_skill: Option<i32>,
+    pub defense_played: Option<i32>,
+    pub defense_resilience: Option<i32>,
+    pub speed: Option<i32>,
+    pub penalties_drawn: Option<i32>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[table_name="event_teams"]
+pub struct EventTeam {
+    pub team: i32,
+}
+
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[table_name="pit_data"]
+pub struct PitReport {
+    pub team: i32,
+    pub drivetrain: String,
+    pub weight: Option<i32>,
+    pub shooter: String,
+    pub climb: Climb,
+    pub programming_language: String,
+    pub notes: String,
+    pub scout: String,
+    pub submitted_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[table_name="pit_photos"]
+pub struct PitPhoto {
+    pub filename: String,
+    pub team: i32,
+    pub uploaded_at: i64,
+}
+
+/// One shot tapped on the field diagram, see the shot_locations migration for the coordinates
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[table_name="shots"]
+pub struct ShotLocation {
+    pub team: i32,
+    pub match_number: i32,
+    pub scout: String,
+    pub sequence: i32,
+    pub phase: Phase,
+    pub x: f32,
+    pub y: f32,
+    pub made: bool,
+}
+
+/// One button press from live scouting mode, with the time in seconds from the start of the match
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[table_name="match_events"]
+pub struct MatchEvent {
+    pub team: i32,
+    pub match_number: i32,
+    pub scout: String,
+    pub sequence: i32,
+    pub time: f32,
+    pub kind: EventKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[table_name="schedule"]
+pub struct ScheduledMatch {
+    pub match_number: i32,
+    pub red1: i32,
+    pub red2: i32,
+    pub red3: i32,
+    pub blue1: i32,
+    pub blue2: i32,
+    pub blue3: i32,
+}
+
+impl ScheduledMatch {
+    pub fn red(&self) -> [i32; 3] {
+        [self.red1, self.red2, self.red3]
+    }
+
+    pub fn blue(&self) -> [i32; 3] {
+        [self.blue1, self.blue2, self.blue3]
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+use diesel::prelude::*;
+use diesel::sqlite::SqliteConnection;
+use actix_web::{HttpRequest, HttpResponse, web, error::ErrorInternalServerError};
+use actix_multipart::Multipart;
+use askama::Template;
+use futures_util::TryStreamExt;
+use log::info;
+
+use crate::{DbPool, HttpResult, DatabaseError, SCOUT_COOKIE};
+use crate::models::{PitReport, PitPhoto};
+use crate::schema::Climb;
+use crate::validation::{self, FormErrors, RawPitReport, SubmitError, ValidationError};
+
+/// where uploaded robot photos are kept, relative to the working directory like the database
+pub const PHOTO_DIR: &str = "photos";
+const MAX_PHOTO_MB: usize = 10;
+// nothing typed into the form should come anywhere near this
+const MAX_TEXT_BYTES: usize = 64 * 1024;
+
+#[derive(Template)]
+#[template(path = "pit-form.html")]
+pub struct PitForm {
+    form: RawPitReport,
+    errors: FormErrors,
+}
+
+impl PitForm {
+    fn to_response(&self, mut builder: actix_web::HttpResponseBuilder) -> HttpResult<HttpResponse> {
+        Ok(builder
+            .content_type("text/html; charset=utf-8")
+            .body(self.render().map_err(ErrorInternalServerError)?))
+    }
+}
+
+/// How a team's climb claim from the pits holds up against what scouts saw in matches
+pub struct ClimbClaim {
+    claimed: Climb,
+    matches: usize,
+    // matches where the robot got at least as high as claimed
+    reached: usize,
+    best: Option<Climb>,
+}
+
+impl ClimbClaim {
+    pub fn new<'a>(claimed: Climb, observed: impl Iterator<Item = &'a Climb>) -> Self {
+        let mut claim = ClimbClaim { claimed, matches: 0, reached: 0, best: None };
+        for climb in observed {
+            claim.matches += 1;
+            if climb.rung() >= claim.claimed.rung() {
+                claim.reached += 1;
+            }
+            if climb.rung() > claim.best.as_ref().map(Climb::rung).unwrap_or(0) {
+                claim.best = Some(climb.clone());
+            }
+        }
+        claim
+    }
+
+    pub fn claimed(&self) -> String {
+        self.claimed.to_string()
+    }
+
+    pub fn matches(&self) -> usize {
+        self.matches
+    }
+
+    pub fn reached(&self) -> usize {
+        self.reached
+    }
+
+    pub fn best(&self) -> String {
+        self.best.as_ref().map(Climb::to_string).unwrap_or_else(|| "None".to_string())
+    }
+
+    /// true when the robot has played but never climbed as high as the team said it could
+    pub fn unconfirmed(&self) -> bool {
+        self.matches > 0 && self.reached == 0
+    }
+
+    pub fn verdict(&self) -> &'static str {
+        let best = self.best.as_ref().map(Climb::rung).unwrap_or(0);
+        if self.claimed.rung() == 0 {
+            if best > 0 { "climbed even though no climb was claimed" } else { "no climb claimed" }
+        } else if self.matches == 0 {
+            "not scouted in a match yet"
+        } else if self.reached == 0 {
+            "never seen reaching the claimed rung"
+        } else if best > self.claimed.rung() {
+            "climbed higher than claimed"
+        } else {
+            "confirmed"
+        }
+    }
+}
+
+pub struct ClaimRow {
+    team: i32,
+    claim: ClimbClaim,
+}
+
+#[derive(Template)]
+#[template(path = "pit-claims.html")]
+pub struct PitClaims {
+    rows: Vec<ClaimRow>,
+}
+
+/// What's been gathered about a team in the pits, for showing on its team page
+pub struct TeamPitData {
+    pub report: Option<PitReport>,
+    pub photos: Vec<PitPhoto>,
+}
+
+pub fn load_team_pit_data(team_number: i32, conn: &SqliteConnection) -> Result<TeamPitData, DatabaseError> {
+    let report = {
+        use crate::schema::pit_data::dsl::*;
+        pit_data.filter(team.eq(team_number)).first(conn).optional()?
+    };
+    let photos = {
+        use crate::schema::pit_photos::dsl::*;
+        pit_photos.filter(team.eq(team_number)).order_by(uploaded_at.desc()).load(conn)?
+    };
+    Ok(TeamPitData { report, photos })
+}
+
+/// show an empty pit scouting form, with the team filled in when coming from a team page
+pub async fn get_pit_form(req: HttpRequest, query: web::Query<BTreeMap<String, String>>) -> HttpResult<HttpResponse> {
+    let form = RawPitReport {
+        scout: req.cookie(SCOUT_COOKIE).map(|c| c.value().to_string()).unwrap_or_default(),
+        team: query.get("team").cloned().unwrap_or_default(),
+        ..Default::default()
+    };
+    PitForm { form, errors: Default::default() }.to_response(HttpResponse::Ok())
+}
+
+/// file extension to save an uploaded photo with, if it's a kind of image browsers can show
+fn photo_extension(content_type: Option<&str>) -> Option<&'static str> {
+    match content_type {
+        Some("image/jpeg") => Some("jpg"),
+        Some("image/png") => Some("png"),
+        Some("image/webp") => Some("webp"),
+        _ => None,
+    }
+}
+
+/// handle the multipart POST from the pit form, which can include a photo of the robot
+pub async fn handle_pit_submit(pool: web::Data<DbPool>, mut payload: Multipart) -> HttpResult<HttpResponse> {
+    let mut form = RawPitReport::default();
+    let mut photo: Option<(web::Bytes, &'static str)> = None;
+    let mut photo_error = None;
+
+    while let Some(mut field) = payload.try_next().await? {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "photo" {
+            let extension = photo_extension(field.content_type().map(|m| m.essence_str()));
+            match field.bytes(MAX_PHOTO_MB * 1024 * 1024).await {
+                Ok(bytes) => {
+                    let bytes = bytes?;
+                    // an empty file input still gets sent, it just doesn't have anything in it
+                    if bytes.is_empty() {
+                        continue;
+                    }
+                    match extension {
+                        Some(extension) => photo = Some((bytes, extension)),
+                        None => photo_error = Some(ValidationError::PhotoType),
+                    }
+                },
+                // the rest of the file gets read and thrown away, so the fields after it still come through
+                Err(_) => photo_error = Some(ValidationError::PhotoTooLarge { max_mb: MAX_PHOTO_MB }),
+            }
+        } else {
+            let bytes = field.bytes(MAX_TEXT_BYTES).await
+                .map_err(|_| actix_web::error::ErrorPayloadTooLarge("form field too large"))??;
+            form.set(&name, String::from_utf8_lossy(&bytes).into_owned());
+        }
+    }
+
+    let mut report = match form.parse() {
+        Ok(report) if photo_error.is_none() => report,
+        result => {
+            let mut errors = result.err().unwrap_or_default();
+            if let Some(e) = photo_error {
+                errors.add(e);
+            }
+            return PitForm { form, errors }.to_response(HttpResponse::BadRequest());
+        },
+    };
+
+    report.submitted_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    info!("Saving pit report for team {} from {}", report.team, report.scout);
+
+    let result = web::block({
+        let pool = pool.clone();
+        let report = report.clone();
+        move || -> Result<(), SubmitError> {
+            use crate::schema::pit_data;
+            let conn = pool.get()?;
+            validation::validate_team(report.team, &conn)?;
+            // the newest report for a team replaces whatever was there
+            diesel::replace_into(pit_data::table).values(&report).execute(&conn)?;
+            Ok(())
+        }
+    }).await.map_err(ErrorInternalServerError)?;
+
+    match result {
+        Ok(()) => {},
+        Err(SubmitError::Invalid(e)) => return PitForm { form, errors: e.into() }.to_response(HttpResponse::BadRequest()),
+        Err(e) => return Err(ErrorInternalServerError(e)),
+    }
+
+    if let Some((bytes, extension)) = photo {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let record = PitPhoto {
+            filename: format!("{}-{}.{}", report.team, nanos, extension),
+            team: report.team,
+            uploaded_at: report.submitted_at,
+        };
+
+        let path = std::path::Path::new(PHOTO_DIR).join(&record.filename);
+        web::block(move || std::fs::write(path, bytes))
+            .await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+        web::block(move || -> Result<(), DatabaseError> {
+            use crate::schema::pit_photos;
+            let conn = pool.get()?;
+            diesel::insert_into(pit_photos::table).values(&record).execute(&conn)?;
+            Ok(())
+        }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+    }
+
+    Ok(HttpResponse::Found()
+        .append_header(("Location", format!("/team/{}", report.team))).finish())
+}
+
+/// every team's climb claim next to what they've actually done in matches
+pub async fn get_pit_claims(pool: web::Data<DbPool>) -> HttpResult<HttpResponse> {
+    let (reports, observed) = web::block(move || -> Result<_, DatabaseError> {
+        let conn = pool.get()?;
+        let reports: Vec<PitReport> = {
+            use crate::schema::pit_data::dsl::*;
+            pit_data.order_by(team.asc()).load(&conn)?
+        };
+        let observed = {
+            use crate::schema::data::dsl::*;
+            data.select((team, climb)).load::<(i32, Climb)>(&conn)?
+        };
+        Ok((reports, observed))
+    }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?;
+
+    let mut climbs: BTreeMap<i32, Vec<Climb>> = BTreeMap::new();
+    for (team, climb) in observed {
+        climbs.entry(team).or_default().push(climb);
+    }
+
+    let mut rows: Vec<ClaimRow> = reports.into_iter()
+        .map(|report| ClaimRow {
+            team: report.team,
+            claim: ClimbClaim::new(report.climb, climbs.get(&report.team).into_iter().flatten()),
+        })
+        .collect();
+    // the claims that don't hold up are the ones worth talking about
+    rows.sort_by_key(|row| !row.claim.unconfirmed());
+
+    let page = PitClaims { rows };
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(page.render().map_err(ErrorInternalServerError)?)
+    )
+}
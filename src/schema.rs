@@ -1,4 +1,5 @@
 use std::fmt::Display;
+use std::str::FromStr;
 use diesel_derive_enum::DbEnum;
 use serde::{Serialize, Deserialize};
 
@@ -15,6 +16,18 @@ impl Display for Alliance {
     }
 }
 
+// parses the same names that Display prints, which are also the form values
+impl FromStr for Alliance {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Red" => Ok(Alliance::Red),
+            "Blue" => Ok(Alliance::Blue),
+            _ => Err(()),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, DbEnum)]
 pub enum LeftTarmac {
     Yes,
@@ -27,6 +40,17 @@ impl Display for LeftTarmac {
     }
 }
 
+impl FromStr for LeftTarmac {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Yes" => Ok(LeftTarmac::Yes),
+            "No" => Ok(LeftTarmac::No),
+            _ => Err(()),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, DbEnum)]
 pub enum Climb {
     No,
@@ -43,10 +67,115 @@ impl Display for Climb {
     }
 }
 
+impl FromStr for Climb {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "No" => Ok(Climb::No),
+            "Failed" => Ok(Climb::Failed),
+            "Low" => Ok(Climb::Low),
+            "Mid" => Ok(Climb::Mid),
+            "High" => Ok(Climb::High),
+            "Traversal" => Ok(Climb::Traversal),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, DbEnum)]
+pub enum Phase {
+    Auto,
+    Teleop,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for Phase {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Auto" => Ok(Phase::Auto),
+            "Teleop" => Ok(Phase::Teleop),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Phase {
+    /// the phase a time in seconds from the start of the match falls in
+    pub fn at(seconds: f32) -> Phase {
+        if seconds < AUTO_SECONDS { Phase::Auto } else { Phase::Teleop }
+    }
+}
+
+/// length of the autonomous period, teleop starts right after it
+pub const AUTO_SECONDS: f32 = 15.0;
+/// auto plus teleop, the log can run a little past this since the buzzer isn't exact
+pub const MATCH_SECONDS: f32 = 150.0;
+
+/// A button pressed in live scouting mode
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, DbEnum)]
+pub enum EventKind {
+    HighMade,
+    HighMissed,
+    LowMade,
+    LowMissed,
+    ClimbStart,
+    ClimbEnd,
+    DefenseStart,
+    DefenseEnd,
+}
+
+impl Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for EventKind {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "HighMade" => Ok(EventKind::HighMade),
+            "HighMissed" => Ok(EventKind::HighMissed),
+            "LowMade" => Ok(EventKind::LowMade),
+            "LowMissed" => Ok(EventKind::LowMissed),
+            "ClimbStart" => Ok(EventKind::ClimbStart),
+            "ClimbEnd" => Ok(EventKind::ClimbEnd),
+            "DefenseStart" => Ok(EventKind::DefenseStart),
+            "DefenseEnd" => Ok(EventKind::DefenseEnd),
+            _ => Err(()),
+        }
+    }
+}
+
+impl EventKind {
+    pub fn is_shot(&self) -> bool {
+        matches!(self, EventKind::HighMade | EventKind::HighMissed | EventKind::LowMade | EventKind::LowMissed)
+    }
+}
+
+impl Climb {
+    /// how far up the hangar the robot got, with no climb and a failed climb both at 0
+    pub fn rung(&self) -> u8 {
+        match self {
+            Climb::No | Climb::Failed => 0,
+            Climb::Low => 1,
+            Climb::Mid => 2,
+            Climb::High => 3,
+            Climb::Traversal => 4,
+        }
+    }
+}
+
 table! {
-    use diesel::sql_types::{Integer, Text};
+    use diesel::sql_types::{BigInt, Integer, Nullable, Text};
     use super::{AllianceMapping, LeftTarmacMapping, ClimbMapping};
-    data (team, match_number) {
+    data (team, match_number, scout) {
         team -> Integer,
         match_number -> Integer,
         alliance -> AllianceMapping,
@@ -61,5 +190,82 @@ table! {
         teleop_low_missed -> Integer,
         climb -> ClimbMapping,
         notes -> Text,
+        scout -> Text,
+        submitted_at -> BigInt,
+        driver_skill -> Nullable<Integer>,
+        defense_played -> Nullable<Integer>,
+        defense_resilience -> Nullable<Integer>,
+        speed -> Nullable<Integer>,
+        penalties_drawn -> Nullable<Integer>,
+    }
+}
+
+table! {
+    event_teams (team) {
+        team -> Integer,
+    }
+}
+
+table! {
+    use diesel::sql_types::{BigInt, Integer, Nullable, Text};
+    use super::ClimbMapping;
+    pit_data (team) {
+        team -> Integer,
+        drivetrain -> Text,
+        weight -> Nullable<Integer>,
+        shooter -> Text,
+        climb -> ClimbMapping,
+        programming_language -> Text,
+        notes -> Text,
+        scout -> Text,
+        submitted_at -> BigInt,
+    }
+}
+
+table! {
+    pit_photos (filename) {
+        filename -> Text,
+        team -> Integer,
+        uploaded_at -> BigInt,
+    }
+}
+
+table! {
+    use diesel::sql_types::{Bool, Float, Integer, Text};
+    use super::PhaseMapping;
+    shots (team, match_number, scout, sequence) {
+        team -> Integer,
+        match_number -> Integer,
+        scout -> Text,
+        sequence -> Integer,
+        phase -> PhaseMapping,
+        x -> Float,
+        y -> Float,
+        made -> Bool,
+    }
+}
+
+table! {
+    use diesel::sql_types::{Float, Integer, Text};
+    use super::EventKindMapping;
+    match_events (team, match_number, scout, sequence) {
+        team -> Integer,
+        match_number -> Integer,
+        scout -> Text,
+        sequence -> Integer,
+        time -> Float,
+        kind -> EventKindMapping,
+    }
+}
+
+table! {
+    schedule (match_number) {
+        match_number -> Integer,
+        red1 -> Integer,
+        red2 -> Integer,
+        red3 -> Integer,
+        blue1 -> Integer,
+        blue2 -> Integer,
+        blue3 -> Integer,
     }
 }
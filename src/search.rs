@@ -0,0 +1,162 @@
+use diesel::prelude::*;
+use diesel::sql_types::{Integer, Text};
+use actix_web::{HttpResponse, web, error::ErrorInternalServerError};
+use askama::Template;
+use serde::Deserialize;
+
+use crate::{DbPool, HttpResult, DatabaseError};
+
+const MAX_RESULTS: i64 = 100;
+// fts5 wraps the matching words in these, they get turned into highlighted pieces afterwards
+const HIGHLIGHT_START: char = '\u{2}';
+const HIGHLIGHT_END: char = '\u{3}';
+
+#[derive(QueryableByName)]
+struct NotesRow {
+    #[sql_type = "Integer"]
+    team: i32,
+    #[sql_type = "Integer"]
+    match_number: i32,
+    #[sql_type = "Text"]
+    scout: String,
+    #[sql_type = "Text"]
+    snippet: String,
+}
+
+/// A piece of a snippet, either plain text or a word that matched the search
+pub struct SnippetPart {
+    text: String,
+    highlighted: bool,
+}
+
+pub struct SearchResult {
+    team: i32,
+    match_number: i32,
+    scout: String,
+    snippet: Vec<SnippetPart>,
+}
+
+#[derive(Template)]
+#[template(path = "search.html")]
+pub struct SearchPage {
+    query: String,
+    results: Vec<SearchResult>,
+}
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    q: String,
+}
+
+/// Turn whatever the user typed into a query fts5 can't choke on.
+/// Every word becomes a quoted phrase, so all of them have to be in the note,
+/// and a trailing * still works for prefix searches like "defen*".
+fn fts_query(input: &str) -> String {
+    input.split_whitespace()
+        .filter_map(|word| {
+            // quotes around a word (as in "tipped over") are just the user quoting it
+            let word = word.trim_matches('"');
+            let (word, prefix) = match word.strip_suffix('*') {
+                Some(w) => (w.trim_matches('"'), "*"),
+                None => (word, ""),
+            };
+            if word.is_empty() {
+                return None;
+            }
+            Some(format!("\"{}\"{}", word.replace('"', "\"\""), prefix))
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn split_snippet(snippet: &str) -> Vec<SnippetPart> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for c in snippet.chars() {
+        if c == HIGHLIGHT_START || c == HIGHLIGHT_END {
+            if !current.is_empty() {
+                parts.push(SnippetPart { text: std::mem::take(&mut current), highlighted: c == HIGHLIGHT_END });
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        parts.push(SnippetPart { text: current, highlighted: false });
+    }
+    parts
+}
+
+pub async fn get_search(pool: web::Data<DbPool>, query: web::Query<SearchQuery>) -> HttpResult<HttpResponse> {
+    let query = query.into_inner().q;
+    let search = fts_query(&query);
+
+    let results = if search.is_empty() {
+        Vec::new()
+    } else {
+        web::block(move || -> Result<Vec<NotesRow>, DatabaseError> {
+            let conn = pool.get()?;
+            Ok(diesel::sql_query(format!(
+                "select team, match_number, scout, snippet(notes_fts, 0, '{}', '{}', '…', 16) as snippet \
+                 from notes_fts where notes_fts match ? order by rank limit {}",
+                HIGHLIGHT_START, HIGHLIGHT_END, MAX_RESULTS))
+                .bind::<Text, _>(search)
+                .load(&conn)?)
+        }).await.map_err(ErrorInternalServerError)?.map_err(ErrorInternalServerError)?
+    };
+
+    let page = SearchPage {
+        query,
+        results: results.into_iter().map(|row| SearchResult {
+            team: row.team,
+            match_number: row.match_number,
+            scout: row.scout,
+            snippet: split_snippet(&row.snippet),
+        }).collect(),
+    };
+
+    Ok(
+        HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(page.render().map_err(ErrorInternalServerError)?)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_every_word() {
+        assert_eq!(fts_query("tipped  over"), "\"tipped\" \"over\"");
+        assert_eq!(fts_query(""), "");
+        assert_eq!(fts_query("   "), "");
+    }
+
+    #[test]
+    fn keeps_prefix_searches() {
+        assert_eq!(fts_query("defen*"), "\"defen\"*");
+        assert_eq!(fts_query("\"defen*\""), "\"defen\"*");
+    }
+
+    #[test]
+    fn strips_quotes_around_words() {
+        assert_eq!(fts_query("\"tipped"), "\"tipped\"");
+        assert_eq!(fts_query("\"tipped over\""), "\"tipped\" \"over\"");
+        assert_eq!(fts_query("6\"wheels"), "\"6\"\"wheels\"");
+    }
+
+    #[test]
+    fn skips_words_with_nothing_left() {
+        assert_eq!(fts_query("\"\" * \"*\" arm"), "\"arm\"");
+    }
+
+    #[test]
+    fn splits_highlighted_snippets() {
+        let parts = split_snippet("fast \u{2}defense\u{3} bot");
+        let parts: Vec<(&str, bool)> = parts.iter().map(|p| (p.text.as_str(), p.highlighted)).collect();
+        assert_eq!(parts, vec![("fast ", false), ("defense", true), (" bot", false)]);
+        assert!(split_snippet("").is_empty());
+    }
+}
@@ -0,0 +1,607 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel::sqlite::SqliteConnection;
+use serde::Deserialize;
+use crate::{models::{RobotMatchInfo, PitReport, ShotLocation, MatchEvent}, DatabaseError};
+use crate::schema::{Phase, EventKind, MATCH_SECONDS};
+
+// a robot starts with one cargo and can maybe grab a few more in 15 seconds,
+// so anything past these is almost certainly a typo or a stuck button
+const MAX_AUTO_SHOTS: i32 = 10;
+const MAX_TELEOP_SHOTS: i32 = 60;
+
+/// The 1 to 5 ratings on the form as (field, label, what 1 and 5 mean)
+pub const RATINGS: [(&str, &str, &str); 5] = [
+    ("driver_skill", "Driver skill", "1 = lost, 5 = very smooth"),
+    ("defense_played", "Defense played", "1 = barely, 5 = shut someone down"),
+    ("defense_resilience", "Defense resilience", "1 = stopped cold, 5 = unaffected"),
+    ("speed", "Speed", "1 = slow, 5 = fastest on the field"),
+    ("penalties_drawn", "Penalties drawn", "1 = none, 5 = lots"),
+];
+
+/// Problems with a submission that the scout can fix, as opposed to server errors
+#[derive(thiserror::Error, Debug)]
+pub enum ValidationError {
+    #[error("{label} is required")]
+    Missing { field: &'static str, label: &'static str },
+    #[error("pick {label}")]
+    Unselected { field: &'static str, label: &'static str },
+    #[error("{label} has to be a whole number")]
+    NotANumber { field: &'static str, label: &'static str },
+    #[error("{label} can't be negative")]
+    Negative { field: &'static str, label: &'static str },
+    #[error("{label} has to be a positive number")]
+    NotPositive { field: &'static str, label: &'static str },
+    #[error("{label} has to be from 1 to 5")]
+    NotARating { field: &'static str, label: &'static str },
+    #[error("the live event log couldn't be read, start live mode again to replace it")]
+    BadEventLog,
+    #[error("some of the shot locations couldn't be read, undo the last few and tap them again")]
+    BadShotLocations,
+    #[error("{count} {phase} shots is more than a robot can take in one match (the limit is {max}), check the counters")]
+    TooManyShots { phase: &'static str, count: i32, max: i32 },
+    #[error("team {0} isn't on the event team list, check the team number")]
+    UnknownTeam(i32),
+    #[error("{scout} already submitted team {team} in match {match_number}")]
+    AlreadySubmitted { team: i32, match_number: i32, scout: String },
+    #[error("the photo has to be a JPEG, PNG or WebP image")]
+    PhotoType,
+    #[error("the photo is bigger than {max_mb} MB, try a smaller one")]
+    PhotoTooLarge { max_mb: usize },
+}
+
+impl ValidationError {
+    /// the name of the form field the error should be shown next to
+    pub fn field(&self) -> &'static str {
+        match self {
+            ValidationError::Missing { field, .. } => field,
+            ValidationError::Unselected { field, .. } => field,
+            ValidationError::NotANumber { field, .. } => field,
+            ValidationError::Negative { field, .. } => field,
+            ValidationError::NotPositive { field, .. } => field,
+            ValidationError::NotARating { field, .. } => field,
+            ValidationError::BadShotLocations => "shots",
+            ValidationError::BadEventLog => "events",
+            ValidationError::TooManyShots { phase, .. } => phase,
+            ValidationError::UnknownTeam(_) => "team",
+            ValidationError::AlreadySubmitted { .. } => "match_number",
+            ValidationError::PhotoType | ValidationError::PhotoTooLarge { .. } => "photo",
+        }
+    }
+}
+
+/// Error messages for a form, keyed by the field they belong to
+#[derive(Default)]
+pub struct FormErrors(BTreeMap<&'static str, String>);
+
+impl FormErrors {
+    pub fn add(&mut self, error: ValidationError) {
+        // only keep the first problem with each field so the form doesn't get cluttered
+        self.0.entry(error.field()).or_insert_with(|| error.to_string());
+    }
+
+    pub fn get(&self, field: &str) -> &str {
+        self.0.get(field).map(String::as_str).unwrap_or("")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<ValidationError> for FormErrors {
+    fn from(error: ValidationError) -> Self {
+        let mut errors = FormErrors::default();
+        errors.add(error);
+        errors
+    }
+}
+
+/// Everything that can go wrong while saving a submission
+#[derive(thiserror::Error, Debug)]
+pub enum SubmitError {
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+}
+
+impl From<r2d2::Error> for SubmitError {
+    fn from(e: r2d2::Error) -> Self {
+        SubmitError::Database(e.into())
+    }
+}
+
+impl From<DieselError> for SubmitError {
+    fn from(e: DieselError) -> Self {
+        SubmitError::Database(e.into())
+    }
+}
+
+/// The form exactly as the browser sent it.
+/// Everything is kept as text so a bad field can't lose the rest of the scout's data
+/// and the form can be filled back in when something needs fixing.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct RawSubmission {
+    pub scout: String,
+    pub team: String,
+    pub match_number: String,
+    pub alliance: String,
+    pub left_tarmac: String,
+    pub auto_high_made: String,
+    pub auto_high_missed: String,
+    pub auto_low_made: String,
+    pub auto_low_missed: String,
+    pub teleop_high_made: String,
+    pub teleop_high_missed: String,
+    pub teleop_low_made: String,
+    pub teleop_low_missed: String,
+    pub climb: String,
+    pub notes: String,
+    pub driver_skill: String,
+    pub defense_played: String,
+    pub defense_resilience: String,
+    pub speed: String,
+    pub penalties_drawn: String,
+    /// shots tapped on the field diagram as "phase,x,y,made" separated by semicolons, filled in by the form's script
+    pub shots: String,
+    /// button presses from live mode as "kind@seconds" separated by semicolons, also filled in by the script
+    pub events: String,
+}
+
+impl Default for RawSubmission {
+    fn default() -> Self {
+        RawSubmission {
+            scout: String::new(),
+            team: String::new(),
+            match_number: String::new(),
+            alliance: String::new(),
+            left_tarmac: String::new(),
+            auto_high_made: "0".to_string(),
+            auto_high_missed: "0".to_string(),
+            auto_low_made: "0".to_string(),
+            auto_low_missed: "0".to_string(),
+            teleop_high_made: "0".to_string(),
+            teleop_high_missed: "0".to_string(),
+            teleop_low_made: "0".to_string(),
+            teleop_low_missed: "0".to_string(),
+            climb: String::new(),
+            notes: String::new(),
+            driver_skill: String::new(),
+            defense_played: String::new(),
+            defense_resilience: String::new(),
+            speed: String::new(),
+            penalties_drawn: String::new(),
+            shots: String::new(),
+            events: String::new(),
+        }
+    }
+}
+
+/// A parsed submission along with the shots that go with it
+pub struct Submission {
+    pub info: RobotMatchInfo,
+    pub shots: Vec<ShotLocation>,
+    pub events: Vec<MatchEvent>,
+}
+
+
+fn parse_number(value: &str, field: &'static str, label: &'static str, errors: &mut FormErrors) -> i32 {
+    let value = value.trim();
+    if value.is_empty() {
+        errors.add(ValidationError::Missing { field, label });
+        return 0;
+    }
+    match value.parse() {
+        Ok(n) => n,
+        Err(_) => {
+            errors.add(ValidationError::NotANumber { field, label });
+            0
+        }
+    }
+}
+
+fn parse_positive(value: &str, field: &'static str, label: &'static str, errors: &mut FormErrors) -> i32 {
+    let n = parse_number(value, field, label, errors);
+    if n <= 0 {
+        errors.add(ValidationError::NotPositive { field, label });
+    }
+    n
+}
+
+/// a counter for one kind of shot, which on its own can't be more than the phase allows
+fn parse_count(value: &str, field: &'static str, label: &'static str, (phase, max): (&'static str, i32), errors: &mut FormErrors) -> i32 {
+    let n = parse_number(value, field, label, errors);
+    if n < 0 {
+        errors.add(ValidationError::Negative { field, label });
+    }
+    if n > max {
+        // capped so adding up the phase total can't overflow
+        errors.add(ValidationError::TooManyShots { phase, count: n, max });
+        return max;
+    }
+    n
+}
+
+/// ratings are optional, so an empty one is fine but anything else has to be 1 to 5
+fn parse_rating(value: &str, field: &'static str, label: &'static str, errors: &mut FormErrors) -> Option<i32> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    match value.parse() {
+        Ok(n) if (1..=5).contains(&n) => Some(n),
+        _ => {
+            errors.add(ValidationError::NotARating { field, label });
+            None
+        }
+    }
+}
+
+fn parse_choice<T: FromStr>(value: &str, field: &'static str, label: &'static str, errors: &mut FormErrors) -> Option<T> {
+    let choice = value.parse().ok();
+    if choice.is_none() {
+        errors.add(ValidationError::Unselected { field, label });
+    }
+    choice
+}
+
+impl RawSubmission {
+    /// read the shot locations from the field diagram without their submission key
+    fn parse_shots(&self) -> Result<Vec<(Phase, f32, f32, bool)>, ValidationError> {
+        let mut shots = Vec::new();
+        for shot in self.shots.split(';').filter(|s| !s.trim().is_empty()) {
+            let parts: Vec<&str> = shot.trim().split(',').collect();
+            let parsed = match parts.as_slice() {
+                [phase, x, y, made] => (phase.parse().ok(), x.parse::<f32>().ok(), y.parse::<f32>().ok(), made.parse::<u8>().ok()),
+                _ => return Err(ValidationError::BadShotLocations),
+            };
+            match parsed {
+                (Some(phase), Some(x), Some(y), Some(made))
+                    if (0f32..=1f32).contains(&x) && (0f32..=1f32).contains(&y) && made <= 1 => shots.push((phase, x, y, made == 1)),
+                _ => return Err(ValidationError::BadShotLocations),
+            }
+        }
+        Ok(shots)
+    }
+
+    /// read the live mode log, in the order the buttons were pressed
+    fn parse_events(&self) -> Result<Vec<(EventKind, f32)>, ValidationError> {
+        let mut events = Vec::new();
+        for event in self.events.split(';').filter(|e| !e.trim().is_empty()) {
+            let parsed = event.trim().split_once('@')
+                .map(|(kind, time)| (kind.parse::<EventKind>().ok(), time.parse::<f32>().ok()));
+            match parsed {
+                // a scout who forgets to stop the timer shouldn't lose the match, but a day-long timer is a bug
+                Some((Some(kind), Some(time))) if (0f32..=2f32 * MATCH_SECONDS).contains(&time) => events.push((kind, time)),
+                _ => return Err(ValidationError::BadEventLog),
+            }
+        }
+        Ok(events)
+    }
+
+    /// Check every field that doesn't need the database and turn the form into database rows
+    pub fn parse(&self) -> Result<Submission, FormErrors> {
+        let mut errors = FormErrors::default();
+
+        let scout = self.scout.trim().to_string();
+        if scout.is_empty() {
+            errors.add(ValidationError::Missing { field: "scout", label: "your name" });
+        }
+        let team = parse_positive(&self.team, "team", "the team number", &mut errors);
+        let match_number = parse_positive(&self.match_number, "match_number", "the match number", &mut errors);
+        let alliance = parse_choice(&self.alliance, "alliance", "an alliance", &mut errors);
+        let left_tarmac = parse_choice(&self.left_tarmac, "left_tarmac", "whether the robot left the tarmac", &mut errors);
+
+        const AUTO: (&str, i32) = ("auto", MAX_AUTO_SHOTS);
+        const TELEOP: (&str, i32) = ("teleop", MAX_TELEOP_SHOTS);
+        let mut auto_high_made = parse_count(&self.auto_high_made, "auto_high_made", "auto high shots", AUTO, &mut errors);
+        let mut auto_high_missed = parse_count(&self.auto_high_missed, "auto_high_missed", "auto high misses", AUTO, &mut errors);
+        let mut auto_low_made = parse_count(&self.auto_low_made, "auto_low_made", "auto low shots", AUTO, &mut errors);
+        let mut auto_low_missed = parse_count(&self.auto_low_missed, "auto_low_missed", "auto low misses", AUTO, &mut errors);
+        let mut teleop_high_made = parse_count(&self.teleop_high_made, "teleop_high_made", "teleop high shots", TELEOP, &mut errors);
+        let mut teleop_high_missed = parse_count(&self.teleop_high_missed, "teleop_high_missed", "teleop high misses", TELEOP, &mut errors);
+        let mut teleop_low_made = parse_count(&self.teleop_low_made, "teleop_low_made", "teleop low shots", TELEOP, &mut errors);
+        let mut teleop_low_missed = parse_count(&self.teleop_low_missed, "teleop_low_missed", "teleop low misses", TELEOP, &mut errors);
+
+        let events = self.parse_events().unwrap_or_else(|e| {
+            errors.add(e);
+            Vec::new()
+        });
+
+        // with a live log the totals come from it, so the counters can't drift from what was logged
+        if !events.is_empty() {
+            let count = |kind: EventKind, phase: Phase| events.iter()
+                .filter(|(k, time)| *k == kind && Phase::at(*time) == phase)
+                .count() as i32;
+            auto_high_made = count(EventKind::HighMade, Phase::Auto);
+            auto_high_missed = count(EventKind::HighMissed, Phase::Auto);
+            auto_low_made = count(EventKind::LowMade, Phase::Auto);
+            auto_low_missed = count(EventKind::LowMissed, Phase::Auto);
+            teleop_high_made = count(EventKind::HighMade, Phase::Teleop);
+            teleop_high_missed = count(EventKind::HighMissed, Phase::Teleop);
+            teleop_low_made = count(EventKind::LowMade, Phase::Teleop);
+            teleop_low_missed = count(EventKind::LowMissed, Phase::Teleop);
+        }
+
+        let auto = auto_high_made + auto_high_missed + auto_low_made + auto_low_missed;
+        if auto > MAX_AUTO_SHOTS {
+            errors.add(ValidationError::TooManyShots { phase: "auto", count: auto, max: MAX_AUTO_SHOTS });
+        }
+        let teleop = teleop_high_made + teleop_high_missed + teleop_low_made + teleop_low_missed;
+        if teleop > MAX_TELEOP_SHOTS {
+            errors.add(ValidationError::TooManyShots { phase: "teleop", count: teleop, max: MAX_TELEOP_SHOTS });
+        }
+
+        let climb = parse_choice(&self.climb, "climb", "how the robot climbed", &mut errors);
+
+        let driver_skill = parse_rating(&self.driver_skill, "driver_skill", "driver skill", &mut errors);
+        let defense_played = parse_rating(&self.defense_played, "defense_played", "defense played", &mut errors);
+        let defense_resilience = parse_rating(&self.defense_resilience, "defense_resilience", "defense resilience", &mut errors);
+        let speed = parse_rating(&self.speed, "speed", "speed", &mut errors);
+        let penalties_drawn = parse_rating(&self.penalties_drawn, "penalties_drawn", "penalties drawn", &mut errors);
+
+        let shots = self.parse_shots().unwrap_or_else(|e| {
+            errors.add(e);
+            Vec::new()
+        });
+
+        let shots = shots.into_iter().enumerate()
+            .map(|(i, (phase, x, y, made))| ShotLocation {
+                team,
+                match_number,
+                scout: scout.clone(),
+                sequence: i as i32,
+                phase,
+                x,
+                y,
+                made,
+            })
+            .collect();
+
+        let events = events.into_iter().enumerate()
+            .map(|(i, (kind, time))| MatchEvent {
+                team,
+                match_number,
+                scout: scout.clone(),
+                sequence: i as i32,
+                time,
+                kind,
+            })
+            .collect();
+
+        match (alliance, left_tarmac, climb) {
+            (Some(alliance), Some(left_tarmac), Some(climb)) if errors.is_empty() => Ok(Submission { shots, events, info: RobotMatchInfo {
+                team,
+                match_number,
+                alliance,
+                left_tarmac,
+                auto_high_made,
+                auto_high_missed,
+                auto_low_made,
+                auto_low_missed,
+                teleop_high_made,
+                teleop_high_missed,
+                teleop_low_made,
+                teleop_low_missed,
+                climb,
+                notes: self.notes.clone(),
+                scout,
+                // filled in when it's actually saved
+                submitted_at: 0,
+                driver_skill,
+                defense_played,
+                defense_resilience,
+                speed,
+                penalties_drawn,
+            }}),
+            _ => Err(errors),
+        }
+    }
+
+    /// the current value of one of the [`RATINGS`] fields, for filling the form back in
+    pub fn rating(&self, field: &str) -> &str {
+        match field {
+            "driver_skill" => &self.driver_skill,
+            "defense_played" => &self.defense_played,
+            "defense_resilience" => &self.defense_resilience,
+            "speed" => &self.speed,
+            "penalties_drawn" => &self.penalties_drawn,
+            _ => "",
+        }
+    }
+}
+
+/// The pit scouting form as the browser sent it, kept as text for the same reasons as [`RawSubmission`]
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct RawPitReport {
+    pub scout: String,
+    pub team: String,
+    pub drivetrain: String,
+    pub weight: String,
+    pub shooter: String,
+    pub climb: String,
+    pub programming_language: String,
+    pub notes: String,
+}
+
+impl RawPitReport {
+    /// Fill in one field from a multipart upload, ignoring names that aren't on the form
+    pub fn set(&mut self, name: &str, value: String) {
+        let field = match name {
+            "scout" => &mut self.scout,
+            "team" => &mut self.team,
+            "drivetrain" => &mut self.drivetrain,
+            "weight" => &mut self.weight,
+            "shooter" => &mut self.shooter,
+            "climb" => &mut self.climb,
+            "programming_language" => &mut self.programming_language,
+            "notes" => &mut self.notes,
+            _ => return,
+        };
+        *field = value;
+    }
+
+    /// Check the fields and turn the form into a database row
+    pub fn parse(&self) -> Result<PitReport, FormErrors> {
+        let mut errors = FormErrors::default();
+
+        let scout = self.scout.trim().to_string();
+        if scout.is_empty() {
+            errors.add(ValidationError::Missing { field: "scout", label: "your name" });
+        }
+        let team = parse_positive(&self.team, "team", "the team number", &mut errors);
+        let drivetrain = self.drivetrain.trim().to_string();
+        if drivetrain.is_empty() {
+            errors.add(ValidationError::Missing { field: "drivetrain", label: "the drivetrain" });
+        }
+        // teams don't always know their weight, so this one can be left empty
+        let weight = if self.weight.trim().is_empty() {
+            None
+        } else {
+            Some(parse_positive(&self.weight, "weight", "the weight", &mut errors))
+        };
+        let climb = parse_choice(&self.climb, "climb", "how high they say they can climb", &mut errors);
+
+        match climb {
+            Some(climb) if errors.is_empty() => Ok(PitReport {
+                team,
+                drivetrain,
+                weight,
+                shooter: self.shooter.trim().to_string(),
+                climb,
+                programming_language: self.programming_language.trim().to_string(),
+                notes: self.notes.clone(),
+                scout,
+                // filled in when it's actually saved
+                submitted_at: 0,
+            }),
+            _ => Err(errors),
+        }
+    }
+}
+
+/// Check the team against the event team list (if one has been entered)
+pub fn validate_team(number: i32, conn: &SqliteConnection) -> Result<(), SubmitError> {
+    use crate::schema::event_teams::dsl::*;
+    let teams: Vec<i32> = event_teams.select(team).load(conn)?;
+    // an empty list means nobody has entered the teams yet, so don't block scouting on it
+    if !teams.is_empty() && !teams.contains(&number) {
+        return Err(ValidationError::UnknownTeam(number).into());
+    }
+    Ok(())
+}
+
+/// Insert a submission and its shots, turning a duplicate key into an error the scout can understand
+pub fn insert_submission(submission: &Submission, conn: &SqliteConnection) -> Result<(), SubmitError> {
+    use crate::schema::{data, shots, match_events};
+    let info = &submission.info;
+    let result = conn.transaction::<_, DieselError, _>(|| {
+        diesel::insert_into(data::table).values(info).execute(conn)?;
+        // sqlite can't do batch inserts in diesel 1.x
+        for shot in submission.shots.iter() {
+            diesel::insert_into(shots::table).values(shot).execute(conn)?;
+        }
+        for event in submission.events.iter() {
+            diesel::insert_into(match_events::table).values(event).execute(conn)?;
+        }
+        Ok(())
+    });
+    match result {
+        Ok(()) => Ok(()),
+        Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => Err(ValidationError::AlreadySubmitted {
+            team: info.team,
+            match_number: info.match_number,
+            scout: info.scout.clone(),
+        }.into()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission() -> RawSubmission {
+        RawSubmission {
+            scout: "Sam".to_string(),
+            team: "33".to_string(),
+            match_number: "4".to_string(),
+            alliance: "Red".to_string(),
+            left_tarmac: "Yes".to_string(),
+            climb: "Mid".to_string(),
+            ..RawSubmission::default()
+        }
+    }
+
+    #[test]
+    fn parses_a_complete_form() {
+        let parsed = submission().parse().ok().unwrap();
+        assert_eq!(parsed.info.team, 33);
+        assert_eq!(parsed.info.auto_high_made, 0);
+        assert!(parsed.shots.is_empty());
+        assert!(parsed.events.is_empty());
+    }
+
+    #[test]
+    fn reports_missing_and_bad_fields() {
+        let raw = RawSubmission { scout: " ".to_string(), team: "abc".to_string(), match_number: "0".to_string(), ..submission() };
+        let errors = raw.parse().err().unwrap();
+        assert_eq!(errors.get("scout"), "your name is required");
+        assert_eq!(errors.get("team"), "the team number has to be a whole number");
+        assert_eq!(errors.get("match_number"), "the match number has to be a positive number");
+    }
+
+    #[test]
+    fn accepts_shots_at_the_limit() {
+        let raw = RawSubmission { auto_high_made: MAX_AUTO_SHOTS.to_string(), teleop_low_missed: MAX_TELEOP_SHOTS.to_string(), ..submission() };
+        assert!(raw.parse().is_ok());
+    }
+
+    #[test]
+    fn rejects_shots_over_the_limit() {
+        let raw = RawSubmission { auto_high_made: "6".to_string(), auto_low_made: "5".to_string(), ..submission() };
+        assert_eq!(raw.parse().err().unwrap().get("auto"), "11 auto shots is more than a robot can take in one match (the limit is 10), check the counters");
+    }
+
+    #[test]
+    fn huge_counters_dont_overflow() {
+        let raw = RawSubmission { auto_high_made: i32::MAX.to_string(), auto_high_missed: "1".to_string(), teleop_high_made: i32::MAX.to_string(), teleop_low_made: i32::MAX.to_string(), ..submission() };
+        let errors = raw.parse().err().unwrap();
+        assert!(errors.get("auto").starts_with("2147483647 auto shots"));
+        assert!(errors.get("teleop").starts_with("2147483647 teleop shots"));
+    }
+
+    #[test]
+    fn rejects_negative_counters() {
+        let raw = RawSubmission { teleop_low_made: "-1".to_string(), ..submission() };
+        assert_eq!(raw.parse().err().unwrap().get("teleop_low_made"), "teleop low shots can't be negative");
+    }
+
+    #[test]
+    fn event_log_replaces_the_counters() {
+        let raw = RawSubmission { auto_high_made: "3".to_string(), events: "HighMade@2;HighMissed@20.5;HighMade@30".to_string(), ..submission() };
+        let info = raw.parse().ok().unwrap().info;
+        assert_eq!((info.auto_high_made, info.teleop_high_made, info.teleop_high_missed), (1, 1, 1));
+    }
+
+    #[test]
+    fn rejects_bad_event_logs_and_shots() {
+        let raw = RawSubmission { events: "HighMade@9999".to_string(), shots: "Auto,0.5,1.5,1".to_string(), ..submission() };
+        let errors = raw.parse().err().unwrap();
+        assert!(!errors.get("events").is_empty());
+        assert!(!errors.get("shots").is_empty());
+    }
+
+    #[test]
+    fn ratings_are_optional_but_bounded() {
+        let raw = RawSubmission { speed: "5".to_string(), driver_skill: "6".to_string(), ..submission() };
+        assert_eq!(raw.parse().err().unwrap().get("driver_skill"), "driver skill has to be from 1 to 5");
+        let raw = RawSubmission { speed: "1".to_string(), ..submission() };
+        assert_eq!(raw.parse().ok().unwrap().info.speed, Some(1));
+    }
+}